@@ -0,0 +1,52 @@
+//! Compares detection over a [`PrefixSet`] scan and a compiled [`PrefixTrie`] as the number of
+//! prefixed or suffixed formats grows.
+
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use prefix_parse::{PrefixFmt, PrefixParse, PrefixSet, PrefixTrie};
+
+/// `count` hexadecimal formats with distinct affixes of letters that are not hexadecimal digits,
+/// such as the prefixes `&gg` and `&hk`, or the suffixes `gg` and `hk`
+fn fmts(count: usize, suffixed: bool) -> &'static [&'static PrefixFmt<'static>] {
+    let fmts: Vec<&'static PrefixFmt<'static>> = (0..count)
+        .map(|index| {
+            let letter = |n: usize| (b'g' + n as u8) as char;
+            let affix = format!("{}{}", letter(index / 20), letter(index % 20));
+            let fmt = match suffixed {
+                true => PrefixFmt::suffixed(Box::leak(affix.into_boxed_str()), 16),
+                false => PrefixFmt::new(Box::leak(format!("&{affix}").into_boxed_str()), 16),
+            };
+            &*Box::leak(Box::new(fmt.unwrap()))
+        })
+        .collect();
+    Box::leak(fmts.into_boxed_slice())
+}
+
+fn detect(c: &mut Criterion, name: &str, suffixed: bool) {
+    let mut group = c.benchmark_group(name);
+    for count in [4, 16, 64, 256] {
+        let set = PrefixSet::new(fmts(count, suffixed)).unwrap();
+        let trie = PrefixTrie::new(&set);
+        // the last format, which a scan reaches last
+        let last = set.fmts()[count - 1];
+        let src = format!("{}DEADBEEF{}", last.prefix(), last.suffix());
+
+        group.bench_with_input(BenchmarkId::new("set", count), &src, |b, src| {
+            b.iter(|| u32::parse_in(&set, black_box(src)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("trie", count), &src, |b, src| {
+            b.iter(|| u32::parse_in(&trie, black_box(src)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn prefixed(c: &mut Criterion) {
+    detect(c, "detect_prefixed", false);
+}
+
+fn suffixed(c: &mut Criterion) {
+    detect(c, "detect_suffixed", true);
+}
+
+criterion_group!(benches, prefixed, suffixed);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+//! Compares parsing prefixed numbers with the `from_str_radix` call underneath.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use prefix_parse::{HEX, ParseOptions, PrefixParse};
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("from_str_radix", |b| {
+        b.iter(|| u32::from_str_radix(black_box("DEADBEEF"), 16).unwrap())
+    });
+    group.bench_function("parse_with", |b| {
+        b.iter(|| u32::parse_with(&HEX, black_box("0xDEADBEEF")).unwrap())
+    });
+    group.bench_function("parse", |b| {
+        b.iter(|| u32::parse(black_box("0xDEADBEEF")).unwrap())
+    });
+    group.bench_function("parse_opts", |b| {
+        let opts = ParseOptions::new();
+        b.iter(|| u32::parse_opts(&opts, black_box("0xDEADBEEF")).unwrap())
+    });
+    group.bench_function("parse_decimal", |b| {
+        b.iter(|| u32::parse(black_box("123456")).unwrap())
+    });
+    group.bench_function("f64_from_str", |b| {
+        b.iter(|| black_box("12.5").parse::<f64>().unwrap())
+    });
+    group.bench_function("f64_parse", |b| {
+        b.iter(|| f64::parse(black_box("12.5")).unwrap())
+    });
+    group.bench_function("f64_parse_hex", |b| {
+        b.iter(|| f64::parse(black_box("0x1.8p3")).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
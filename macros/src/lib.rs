@@ -0,0 +1,102 @@
+//! Procedural macros for [`prefix_parse`](https://docs.rs/prefix_parse), enabled by its `macros`
+//! feature.
+
+use proc_macro::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Ident, LitStr, Token, parse_macro_input};
+
+/// The primitive integer types a literal can be parsed into
+const TYPES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Parse a prefixed literal at compile time, producing a typed constant
+///
+/// Takes the target type, the literal, and optionally a `fmt = <PrefixFmt>` to parse with.
+/// Without a format, prefixes are detected as in `PrefixParse::parse`. Underscores may separate
+/// the digits, and invalid digits or overflow are compile errors at the literal.
+///
+/// # Example
+/// ```
+/// use prefix_parse::PrefixFmt;
+/// use prefix_parse_macros::prefixed;
+///
+/// const BASE36: PrefixFmt = match PrefixFmt::new("0z", 36) {
+///     Ok(fmt) => fmt,
+///     Err(_) => panic!("invalid format"),
+/// };
+///
+/// assert_eq!(prefixed!(u32, "0z1jz", fmt = BASE36), 2015);
+/// assert_eq!(prefixed!(u64, "0x4000_0000"), 0x4000_0000);
+/// assert_eq!(prefixed!(i8, "-0b1000_0000"), i8::MIN);
+/// ```
+///
+/// Invalid digits fail to compile:
+/// ```compile_fail
+/// let value = prefix_parse_macros::prefixed!(u8, "0x1G");
+/// ```
+///
+/// As do values that do not fit the type:
+/// ```compile_fail
+/// let value = prefix_parse_macros::prefixed!(u8, "0x100");
+/// ```
+#[proc_macro]
+pub fn prefixed(input: TokenStream) -> TokenStream {
+    let Prefixed { ty, literal, fmt } = parse_macro_input!(input as Prefixed);
+
+    let fmt = match fmt {
+        Some(fmt) => quote!(::core::option::Option::Some(&#fmt)),
+        None => quote!(::core::option::Option::None),
+    };
+
+    // the value is checked in a const block, panicking at the literal if it is invalid
+    quote_spanned! {literal.span()=>
+        const {
+            match ::prefix_parse::__prefixed_literal(
+                #fmt,
+                #literal,
+                <#ty>::MAX as u128,
+                <#ty>::MIN != 0,
+            ) {
+                ::core::result::Result::Ok((true, magnitude)) => (magnitude as #ty).wrapping_neg(),
+                ::core::result::Result::Ok((false, magnitude)) => magnitude as #ty,
+                ::core::result::Result::Err(message) => ::core::panic!("{}", message),
+            }
+        }
+    }
+    .into()
+}
+
+/// The arguments of [`prefixed!`]: `type, "literal"` and optionally `, fmt = format`
+struct Prefixed {
+    ty: Ident,
+    literal: LitStr,
+    fmt: Option<Expr>,
+}
+
+impl Parse for Prefixed {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty: Ident = input.parse()?;
+        if !TYPES.contains(&ty.to_string().as_str()) {
+            let message = format!("expected a primitive integer type, found `{ty}`");
+            return Err(syn::Error::new(ty.span(), message));
+        }
+
+        input.parse::<Token![,]>()?;
+        let literal = input.parse()?;
+
+        let mut fmt = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "fmt" {
+                return Err(syn::Error::new(key.span(), "expected `fmt = <PrefixFmt>`"));
+            }
+            input.parse::<Token![=]>()?;
+            fmt = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Prefixed { ty, literal, fmt })
+    }
+}
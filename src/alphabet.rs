@@ -0,0 +1,227 @@
+use crate::{FmtError, valid};
+
+/// Defines the digits of a radix, for radices beyond 36 or non-standard digit sets.
+///
+/// Each character's digit value is its position in the alphabet. Alphabets can optionally fold
+/// ASCII case, and map alias characters onto digits, such as Crockford's `I` → `1` and `O` → `0`.
+///
+/// # Example
+/// ```
+/// use prefix_parse::Alphabet;
+///
+/// let octal = Alphabet::new("01234567")?;
+/// assert_eq!(octal.radix(), 8);
+/// assert_eq!(octal.digit_value('7'), Some(7));
+/// assert_eq!(octal.digit_value('8'), None);
+///
+/// assert_eq!(Alphabet::CROCKFORD.digit_value('o'), Some(0));
+/// # Ok::<(), prefix_parse::FmtError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet<'a> {
+    digits: &'a str,
+    radix: u32,
+    aliases: &'a [(char, char)],
+    case_insensitive: bool,
+    /// Digit values of ASCII characters, including folded cases and aliases
+    ascii: [Option<u8>; 128],
+}
+
+impl Alphabet<'static> {
+    /// Bitcoin base 58, omitting `0`, `O`, `I` and `l`
+    pub const BASE58: Alphabet<'static> = valid(Alphabet::new(
+        "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+    ));
+
+    /// Base 62, digits then uppercase then lowercase letters
+    pub const BASE62: Alphabet<'static> = valid(Alphabet::new(
+        "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+    ));
+
+    /// Base 64, using the RFC 4648 standard alphabet
+    pub const BASE64: Alphabet<'static> = valid(Alphabet::new(
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+    ));
+
+    /// Crockford base 32, case-insensitive, reading `I` and `L` as `1` and `O` as `0`
+    pub const CROCKFORD: Alphabet<'static> =
+        match valid(Alphabet::new("0123456789ABCDEFGHJKMNPQRSTVWXYZ")).case_insensitive() {
+            Ok(alphabet) => valid(alphabet.with_aliases(&[('I', '1'), ('L', '1'), ('O', '0')])),
+            Err(_) => panic!("invalid alphabet"),
+        };
+}
+
+impl<'a> Alphabet<'a> {
+    /// Create a new case-sensitive alphabet from its digits, in order of value
+    ///
+    /// # Errors
+    /// - [`FmtError::AlphabetSize`] if there are fewer than 2 or more than 256 digits.
+    /// - [`FmtError::DuplicateDigit`] if a digit appears twice.
+    pub const fn new(digits: &'a str) -> Result<Self, FmtError> {
+        let mut ascii = [None; 128];
+        let mut radix = 0;
+        let mut index = 0;
+        while index < digits.len() {
+            let (c, len) = decode(digits.as_bytes(), index);
+            if find(digits, index, c).is_some() {
+                return Err(FmtError::DuplicateDigit(c));
+            }
+            if c.is_ascii() && radix < 256 {
+                ascii[c as usize] = Some(radix as u8);
+            }
+            radix += 1;
+            index += len;
+        }
+
+        if radix < 2 || radix > 256 {
+            return Err(FmtError::AlphabetSize(radix as usize));
+        }
+
+        Ok(Alphabet {
+            digits,
+            radix,
+            aliases: &[],
+            case_insensitive: false,
+            ascii,
+        })
+    }
+
+    /// Match ASCII letters regardless of case
+    ///
+    /// # Errors
+    /// [`FmtError::DuplicateDigit`] if two digits differ only by case.
+    pub const fn case_insensitive(mut self) -> Result<Self, FmtError> {
+        let mut c = 0;
+        while c < self.ascii.len() {
+            let folded = (c as u8).to_ascii_lowercase() as usize;
+            if folded != c {
+                match (self.ascii[c], self.ascii[folded]) {
+                    (Some(upper), Some(lower)) if upper != lower => {
+                        return Err(FmtError::DuplicateDigit(folded as u8 as char));
+                    }
+                    (Some(value), None) => self.ascii[folded] = Some(value),
+                    (None, Some(value)) => self.ascii[c] = Some(value),
+                    _ => {}
+                }
+            }
+            c += 1;
+        }
+
+        self.case_insensitive = true;
+        Ok(self)
+    }
+
+    /// Add aliases, each read as the digit it is paired with
+    ///
+    /// # Errors
+    /// [`FmtError::InvalidAlias`] if an alias is already a digit, or its target is not.
+    pub const fn with_aliases(mut self, aliases: &'a [(char, char)]) -> Result<Self, FmtError> {
+        let mut index = 0;
+        while index < aliases.len() {
+            let (alias, target) = aliases[index];
+            let value = match self.const_digit_value(target) {
+                Some(value) if self.const_digit_value(alias).is_none() => value,
+                _ => return Err(FmtError::InvalidAlias(alias)),
+            };
+
+            if alias.is_ascii() {
+                self.ascii[alias as usize] = Some(value as u8);
+                if self.case_insensitive {
+                    self.ascii[alias.to_ascii_uppercase() as usize] = Some(value as u8);
+                    self.ascii[alias.to_ascii_lowercase() as usize] = Some(value as u8);
+                }
+            }
+            index += 1;
+        }
+
+        self.aliases = aliases;
+        Ok(self)
+    }
+
+    /// The digits of the alphabet, in order of value
+    pub const fn digits(&self) -> &'a str {
+        self.digits
+    }
+
+    /// The number of digits in the alphabet
+    pub const fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// If true, ASCII letters match regardless of case
+    pub const fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// The digit with value `value`, or `None` if `value` is not below the radix
+    pub fn digit(&self, value: u32) -> Option<char> {
+        self.digits.chars().nth(value as usize)
+    }
+
+    /// The value of the digit `c`, or `None` if `c` is not part of the alphabet
+    pub fn digit_value(&self, c: char) -> Option<u32> {
+        match c.is_ascii() {
+            true => self.ascii[c as usize].map(u32::from),
+            false => self.const_digit_value(c),
+        }
+    }
+
+    pub(crate) const fn const_digit_value(&self, c: char) -> Option<u32> {
+        if c.is_ascii() {
+            return match self.ascii[c as usize] {
+                Some(value) => Some(value as u32),
+                None => None,
+            };
+        }
+        if let Some(value) = find(self.digits, self.digits.len(), c) {
+            return Some(value);
+        }
+
+        let mut index = 0;
+        while index < self.aliases.len() {
+            let (alias, target) = self.aliases[index];
+            if alias == c {
+                return find(self.digits, self.digits.len(), target);
+            }
+            index += 1;
+        }
+        None
+    }
+}
+
+/// Finds the position of `c` among the characters of `digits[..end]`
+const fn find(digits: &str, end: usize, c: char) -> Option<u32> {
+    let mut position = 0;
+    let mut index = 0;
+    while index < end {
+        let (digit, len) = decode(digits.as_bytes(), index);
+        if digit == c {
+            return Some(position);
+        }
+        position += 1;
+        index += len;
+    }
+    None
+}
+
+/// Decodes the UTF-8 character starting at `bytes[index]`, returning it and its length
+pub(crate) const fn decode(bytes: &[u8], index: usize) -> (char, usize) {
+    let lead = bytes[index] as u32;
+    let (mut code, len) = match lead {
+        0x00..=0x7F => return (lead as u8 as char, 1),
+        0xC0..=0xDF => (lead & 0x1F, 2),
+        0xE0..=0xEF => (lead & 0x0F, 3),
+        _ => (lead & 0x07, 4),
+    };
+
+    let mut offset = 1;
+    while offset < len {
+        code = (code << 6) | (bytes[index + offset] as u32 & 0x3F);
+        offset += 1;
+    }
+
+    match char::from_u32(code) {
+        Some(c) => (c, len),
+        None => panic!("invalid UTF-8"),
+    }
+}
@@ -0,0 +1,63 @@
+use core::fmt::{self, Write};
+
+/// Bytes held on the stack before falling back to the heap
+const CAPACITY: usize = 256;
+
+/// A string of digits rewritten for `from_str_radix`, held on the stack.
+///
+/// Digits that outgrow the stack spill to the heap when the `alloc` feature is enabled, and are
+/// otherwise rejected with [`fmt::Error`]. With leading zeros dropped, the stack holds more digits
+/// than any primitive integer can represent, even in binary.
+pub(crate) struct DigitBuf {
+    bytes: [u8; CAPACITY],
+    len: usize,
+    #[cfg(feature = "alloc")]
+    spilled: Option<alloc::string::String>,
+}
+
+impl DigitBuf {
+    pub(crate) fn new() -> Self {
+        DigitBuf {
+            bytes: [0; CAPACITY],
+            len: 0,
+            #[cfg(feature = "alloc")]
+            spilled: None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        #[cfg(feature = "alloc")]
+        if let Some(spilled) = &self.spilled {
+            return spilled;
+        }
+        // only whole `str`s are ever written, so the bytes are always valid UTF-8
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for DigitBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        #[cfg(feature = "alloc")]
+        if let Some(spilled) = &mut self.spilled {
+            spilled.push_str(s);
+            return Ok(());
+        }
+
+        match self.bytes.get_mut(self.len..self.len + s.len()) {
+            Some(bytes) => {
+                bytes.copy_from_slice(s.as_bytes());
+                self.len += s.len();
+                Ok(())
+            }
+            #[cfg(feature = "alloc")]
+            None => {
+                let mut spilled = alloc::string::String::from(self.as_str());
+                spilled.push_str(s);
+                self.spilled = Some(spilled);
+                Ok(())
+            }
+            #[cfg(not(feature = "alloc"))]
+            None => Err(fmt::Error),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! C `strtol`/`strtoul` compatible parsing, with base 0.
+//!
+//! Follows C's rules rather than those of [`PrefixParse::parse`]: leading whitespace is skipped,
+//! `0x` or `0X` marks hexadecimal, a bare leading `0` marks octal, and parsing stops at the first
+//! character that cannot continue the number, returning the unconsumed tail as C's end pointer
+//! does. A prefix without digits is read as the `0` it starts with, so `0x` is zero leaving `x`.
+//!
+//! Where C would convert nothing and return the input as the end pointer, [`parse`] fails with
+//! [`ErrorKind::Empty`].
+//!
+//! # Example
+//! ```
+//! use prefix_parse::{ErrorKind, c};
+//!
+//! let strtol = c::Syntax::new();
+//! assert_eq!(c::parse(&strtol, "  0755 rest"), Ok((493, " rest")));
+//! assert_eq!(c::parse(&strtol, "-0x1Fg"), Ok((-31, "g")));
+//! assert_eq!(c::parse(&strtol, "08"), Ok((0, "8")));
+//! assert_eq!(c::parse(&strtol, "0x"), Ok((0, "x")));
+//!
+//! let c23 = c::Syntax::new().c23().suffixes();
+//! assert_eq!(c::parse(&c23, "0b1010'0101ULL;"), Ok((0xA5u64, ";")));
+//!
+//! let error = c::parse::<u8>(&strtol, "  abc").unwrap_err();
+//! assert_eq!((error.kind(), error.offset()), (ErrorKind::Empty, 2));
+//! ```
+//!
+//! [`PrefixParse::parse`]: crate::PrefixParse::parse
+
+use num_traits::PrimInt;
+
+use crate::{
+    BIN, DEC, ErrorKind, HEX, Overflow, ParseError, ParseOptions, Parts, PrefixFmt, Separators,
+    options, valid,
+};
+
+/// C23 digit separators, accepted only between digits
+const C23_SEPARATORS: Separators<'static> = Separators {
+    chars: &['\''],
+    after_prefix: false,
+    consecutive: false,
+    trailing: false,
+};
+
+/// C's octal prefix, the leading `0` of the number
+const C_OCT: PrefixFmt = valid(PrefixFmt::new("0", 8));
+
+const HEX_C23: PrefixFmt = HEX.with_separators(C23_SEPARATORS);
+const BIN_C23: PrefixFmt = BIN.with_separators(C23_SEPARATORS);
+// the leading `0` of an octal literal is a digit, so a separator may follow it, as in `0'755`
+const OCT_C23: PrefixFmt = C_OCT.with_separators(Separators {
+    after_prefix: true,
+    ..C23_SEPARATORS
+});
+const DEC_C23: PrefixFmt = DEC.with_separators(C23_SEPARATORS);
+
+/// The characters C's `isspace` accepts
+const WHITESPACE: [char; 6] = [' ', '\t', '\n', '\x0B', '\x0C', '\r'];
+
+/// Which C rules to follow, beyond those of `strtol` with base 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Syntax {
+    suffixes: bool,
+    c23: bool,
+    /// The policy for values that do not fit the type, or `None` to follow C
+    overflow: Option<Overflow>,
+}
+
+impl Syntax {
+    /// The rules of `strtol` and `strtoul` with base 0
+    ///
+    /// Values that do not fit the type are handled as C does. Signed types saturate, as `strtol`
+    /// does. Unsigned types negate negative values modulo their width, and saturate magnitudes
+    /// too large for them to the largest value, as `strtoul` does.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{ErrorKind, Overflow, c};
+    ///
+    /// let strtol = c::Syntax::new();
+    /// assert_eq!(c::parse::<i32>(&strtol, "0x80000000"), Ok((i32::MAX, "")));
+    /// assert_eq!(c::parse::<i32>(&strtol, "-0x80000001"), Ok((i32::MIN, "")));
+    /// assert_eq!(c::parse::<u32>(&strtol, "-1"), Ok((u32::MAX, "")));
+    /// assert_eq!(c::parse::<u32>(&strtol, "-0xFFFFFFFF"), Ok((1, "")));
+    /// assert_eq!(c::parse::<u32>(&strtol, "0x100000000"), Ok((u32::MAX, "")));
+    /// assert_eq!(c::parse::<u32>(&strtol, "-0x100000000"), Ok((u32::MAX, "")));
+    /// assert_eq!(c::parse::<u32>(&strtol, "-0"), Ok((0, "")));
+    ///
+    /// let strict = strtol.overflow(Overflow::Error);
+    /// let error = c::parse::<u32>(&strict, "-1").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::NegativeUnsigned);
+    /// ```
+    pub const fn new() -> Self {
+        Syntax {
+            suffixes: false,
+            c23: false,
+            overflow: None,
+        }
+    }
+
+    /// Consume an integer suffix after the digits: `u` and `l` or `ll` in either order, in
+    /// either case, such as `10UL` or `10llu`
+    pub const fn suffixes(self) -> Self {
+        Syntax {
+            suffixes: true,
+            ..self
+        }
+    }
+
+    /// Accept C23 `0b` binary prefixes and `'` digit separators, such as `0b1010'0101`
+    pub const fn c23(self) -> Self {
+        Syntax { c23: true, ..self }
+    }
+
+    /// Handle values that do not fit the type with `overflow`, rather than as C does
+    pub const fn overflow(self, overflow: Overflow) -> Self {
+        Syntax {
+            overflow: Some(overflow),
+            ..self
+        }
+    }
+
+    /// If true, integer suffixes are consumed
+    pub const fn has_suffixes(&self) -> bool {
+        self.suffixes
+    }
+
+    /// If true, C23 binary prefixes and digit separators are accepted
+    pub const fn is_c23(&self) -> bool {
+        self.c23
+    }
+
+    /// The policy for values that do not fit the type, or `None` if they are handled as C does
+    pub const fn overflow_policy(&self) -> Option<Overflow> {
+        self.overflow
+    }
+}
+
+/// Parse the C integer at the front of `src` with `syntax`, returning it with the unconsumed tail
+///
+/// # Errors
+/// As [`PrefixParse::parse_opts`](crate::PrefixParse::parse_opts), with [`ErrorKind::Empty`] if
+/// `src` does not start with a number after any whitespace.
+pub fn parse<'s, T: PrimInt>(
+    syntax: &Syntax,
+    src: &'s str,
+) -> Result<(T, &'s str), ParseError<'static, T>> {
+    let body = src.trim_start_matches(WHITESPACE);
+    let skipped = src.len() - body.len();
+
+    let (fmts, dec): (&[&'static PrefixFmt<'static>], _) = match syntax.c23 {
+        true => (&[&HEX_C23, &BIN_C23, &OCT_C23], &DEC_C23),
+        false => (&[&HEX, &C_OCT], &DEC),
+    };
+    let (fmt, (parts, rest)) = fmts
+        .iter()
+        .find_map(|&fmt| fmt.split_partial(body).map(|split| (fmt, split)))
+        .or_else(|| dec.split_partial(body).map(|split| (dec, split)))
+        .ok_or_else(|| ParseError::at(ErrorKind::Empty, skipped, None))?;
+
+    let (value, rest) = match parts.prefixed() && parts.digits.is_empty() {
+        // the prefix starts with the digit `0`, and nothing follows that can continue it
+        true => (
+            T::zero(),
+            &parts.src[parts.digits_offset() - parts.prefix.len() + 1..],
+        ),
+        false => {
+            let value = match syntax.overflow {
+                Some(overflow) => {
+                    let opts = ParseOptions::new().overflow(overflow);
+                    options::parse_parts(&parts, fmt, &opts).map(|(value, _)| value)
+                }
+                None => strto(&parts, fmt),
+            };
+            (value.map_err(|error| error.offset_by(skipped))?, rest)
+        }
+    };
+
+    match syntax.suffixes {
+        true => Ok((value, &rest[suffix_len(rest)..])),
+        false => Ok((value, rest)),
+    }
+}
+
+/// Parses the digits of `parts` with `fmt` as `strtol` does for signed types, and as `strtoul`
+/// does for unsigned ones
+fn strto<'f, T: PrimInt>(parts: &Parts, fmt: &PrefixFmt<'f>) -> Result<T, ParseError<'f, T>> {
+    let saturate = ParseOptions::new().overflow(Overflow::Saturate);
+    if T::min_value() < T::zero() || !parts.negative {
+        return options::parse_parts(parts, fmt, &saturate).map(|(value, _)| value);
+    }
+
+    // `strtoul` reads the magnitude, then negates it in the unsigned type
+    let magnitude = Parts {
+        negative: false,
+        ..*parts
+    };
+    match options::parse_parts(&magnitude, fmt, &saturate)? {
+        (_, true) => Ok(T::max_value()),
+        (value, false) if value.is_zero() => Ok(value),
+        (value, false) => Ok(T::max_value() - value + T::one()),
+    }
+}
+
+/// The byte length of the integer suffix at the front of `src`, if any
+fn suffix_len(src: &str) -> usize {
+    let bytes = src.as_bytes();
+    let unsigned = |at: usize| usize::from(matches!(bytes.get(at), Some(b'u' | b'U')));
+    let long = |at: usize| match bytes.get(at..at + 2) {
+        Some(b"ll" | b"LL") => 2,
+        _ => usize::from(matches!(bytes.get(at), Some(b'l' | b'L'))),
+    };
+
+    match unsigned(0) {
+        0 => {
+            let long = long(0);
+            long + unsigned(long)
+        }
+        unsigned => unsigned + long(unsigned),
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{BIN, DEC, ErrorKind, HEX, OCT, PrefixFmt, SeparatorError, Separators, UNDERSCORES};
+
+const HEX_LITERAL: PrefixFmt = HEX.with_separators(UNDERSCORES);
+const OCT_LITERAL: PrefixFmt = OCT.with_separators(UNDERSCORES);
+const BIN_LITERAL: PrefixFmt = BIN.with_separators(UNDERSCORES);
+const DEC_LITERAL: PrefixFmt = DEC.with_separators(UNDERSCORES);
+
+macro_rules! const_parse {
+    ($($(#[$attr:meta])* $ty:ident => $parse:ident, $parse_with:ident;)*) => {$(
+        #[doc = concat!("Parse a prefixed `", stringify!($ty), "` in a const context")]
+        ///
+        /// Prefixes and signs are detected as in
+        /// [`PrefixParse::parse`](crate::PrefixParse::parse), and underscores may separate the
+        /// digits, as in Rust literals.
+        ///
+        /// # Panics
+        /// If `src` is not a valid number, or does not fit the type. In a const context this is a
+        /// compile error.
+        $(#[$attr])*
+        pub const fn $parse(src: &str) -> $ty {
+            $parse_with(builtin(src), src)
+        }
+
+        #[doc = concat!("Parse a `", stringify!($ty), "` with a custom format in a const context")]
+        ///
+        /// Signs are handled as in [`PrefixParse::parse_with`](crate::PrefixParse::parse_with).
+        /// Alphabets of any size are supported.
+        ///
+        /// # Panics
+        /// If `src` does not match `fmt`, or does not fit the type. In a const context this is a
+        /// compile error.
+        pub const fn $parse_with(fmt: &PrefixFmt, src: &str) -> $ty {
+            match literal(fmt, src, <$ty>::MAX as u128, <$ty>::MIN != 0) {
+                Ok((true, magnitude)) => (magnitude as $ty).wrapping_neg(),
+                Ok((false, magnitude)) => magnitude as $ty,
+                Err(kind) => panic!("{}", message(kind)),
+            }
+        }
+    )*};
+}
+
+const_parse! {
+    u8 => const_parse_u8, const_parse_u8_with;
+    u16 => const_parse_u16, const_parse_u16_with;
+    /// # Example
+    /// ```
+    /// use prefix_parse::{const_parse_u32, const_parse_u32_with, PrefixFmt};
+    ///
+    /// const BASE36: PrefixFmt = match PrefixFmt::new("0z", 36) {
+    ///     Ok(fmt) => fmt,
+    ///     Err(_) => panic!("invalid format"),
+    /// };
+    ///
+    /// const ID: u32 = const_parse_u32_with(&BASE36, "0z1jz");
+    /// const MASK: u32 = const_parse_u32("0b1111_0000");
+    /// assert_eq!((ID, MASK), (2015, 0xF0));
+    /// ```
+    u32 => const_parse_u32, const_parse_u32_with;
+    /// # Example
+    /// ```
+    /// use prefix_parse::const_parse_u64;
+    ///
+    /// const FLASH_BASE: u64 = const_parse_u64("0x4000_0000");
+    /// assert_eq!(FLASH_BASE, 0x4000_0000);
+    /// ```
+    ///
+    /// Invalid input fails to compile:
+    /// ```compile_fail
+    /// const BAD: u64 = prefix_parse::const_parse_u64("0x4000_000G");
+    /// ```
+    u64 => const_parse_u64, const_parse_u64_with;
+    u128 => const_parse_u128, const_parse_u128_with;
+    usize => const_parse_usize, const_parse_usize_with;
+    i8 => const_parse_i8, const_parse_i8_with;
+    i16 => const_parse_i16, const_parse_i16_with;
+    /// # Example
+    /// ```
+    /// use prefix_parse::const_parse_i32;
+    ///
+    /// const MIN: i32 = const_parse_i32("-0x8000_0000");
+    /// assert_eq!(MIN, i32::MIN);
+    /// ```
+    i32 => const_parse_i32, const_parse_i32_with;
+    i64 => const_parse_i64, const_parse_i64_with;
+    i128 => const_parse_i128, const_parse_i128_with;
+    isize => const_parse_isize, const_parse_isize_with;
+}
+
+/// Parses `src` with `fmt`, or the built-in formats, into the sign and magnitude of a type whose
+/// largest value is `max`, for the `prefixed!` macro
+///
+/// Errors are messages, which the macro reports at the literal.
+#[doc(hidden)]
+pub const fn __prefixed_literal(
+    fmt: Option<&PrefixFmt>,
+    src: &str,
+    max: u128,
+    signed: bool,
+) -> Result<(bool, u128), &'static str> {
+    let fmt = match fmt {
+        Some(fmt) => fmt,
+        None => builtin(src),
+    };
+    match literal(fmt, src, max, signed) {
+        Ok(parsed) => Ok(parsed),
+        Err(kind) => Err(message(kind)),
+    }
+}
+
+/// The built-in format whose prefix matches `src`, falling back to decimal
+const fn builtin(src: &str) -> &'static PrefixFmt<'static> {
+    let start = sign_len(src.as_bytes());
+    if prefix_len(&HEX_LITERAL, src.as_bytes(), start).is_some() {
+        &HEX_LITERAL
+    } else if prefix_len(&OCT_LITERAL, src.as_bytes(), start).is_some() {
+        &OCT_LITERAL
+    } else if prefix_len(&BIN_LITERAL, src.as_bytes(), start).is_some() {
+        &BIN_LITERAL
+    } else {
+        &DEC_LITERAL
+    }
+}
+
+/// Parses `src` with `fmt` into the sign and magnitude of a type whose largest value is `max`
+const fn literal(
+    fmt: &PrefixFmt,
+    src: &str,
+    max: u128,
+    signed: bool,
+) -> Result<(bool, u128), ErrorKind> {
+    let (negative, magnitude) = match magnitude(fmt, src) {
+        Ok(parsed) => parsed,
+        Err(kind) => return Err(kind),
+    };
+    match fits(negative, magnitude, max, signed) {
+        Ok(()) => Ok((negative, magnitude)),
+        Err(kind) => Err(kind),
+    }
+}
+
+/// Parses `src` with `fmt` into its sign and magnitude
+const fn magnitude(fmt: &PrefixFmt, src: &str) -> Result<(bool, u128), ErrorKind> {
+    let bytes = src.as_bytes();
+    let negative = matches!(bytes.first(), Some(b'-'));
+    let sign_len = sign_len(bytes);
+
+    let Some(prefix_len) = prefix_len(fmt, bytes, sign_len) else {
+        return Err(ErrorKind::NoPrefixMatch);
+    };
+    let start = sign_len + prefix_len;
+    let Some(end) = suffix_start(fmt, bytes, start) else {
+        return Err(ErrorKind::NoPrefixMatch);
+    };
+    if start < end && matches!(bytes[start], b'+' | b'-') {
+        return Err(ErrorKind::MisplacedSign);
+    }
+
+    let overflow = match negative {
+        true => ErrorKind::NegOverflow,
+        false => ErrorKind::PosOverflow,
+    };
+
+    let mut magnitude: u128 = 0;
+    let mut digits = 0;
+    let mut prev_separator = false;
+    let mut index = start;
+    while index < end {
+        let (c, len) = crate::alphabet::decode(bytes, index);
+        if is_separator(&fmt.separators, c) {
+            let error = match (index == start, prefix_len > 0) {
+                (true, false) => Some(SeparatorError::Leading),
+                (true, true) if !fmt.separators.after_prefix => Some(SeparatorError::AfterPrefix),
+                _ if prev_separator && !fmt.separators.consecutive => {
+                    Some(SeparatorError::Consecutive)
+                }
+                _ => None,
+            };
+            if let Some(error) = error {
+                return Err(ErrorKind::Separator(error));
+            }
+            prev_separator = true;
+        } else {
+            let Some(value) = digit_value(fmt, c) else {
+                return Err(ErrorKind::InvalidDigit);
+            };
+            magnitude = match magnitude.checked_mul(fmt.radix as u128) {
+                Some(shifted) => match shifted.checked_add(value as u128) {
+                    Some(magnitude) => magnitude,
+                    None => return Err(overflow),
+                },
+                None => return Err(overflow),
+            };
+            digits += 1;
+            prev_separator = false;
+        }
+        index += len;
+    }
+
+    match (digits, prev_separator && !fmt.separators.trailing) {
+        (0, _) => Err(ErrorKind::Empty),
+        (_, true) => Err(ErrorKind::Separator(SeparatorError::Trailing)),
+        _ => Ok((negative, magnitude)),
+    }
+}
+
+/// Checks that a magnitude fits a type whose largest value is `max`
+const fn fits(negative: bool, magnitude: u128, max: u128, signed: bool) -> Result<(), ErrorKind> {
+    match (negative, signed) {
+        (true, false) => Err(ErrorKind::NegativeUnsigned),
+        // the most negative value of a signed type has a magnitude one above its maximum
+        (true, true) if magnitude > max + 1 => Err(ErrorKind::NegOverflow),
+        (false, _) if magnitude > max => Err(ErrorKind::PosOverflow),
+        _ => Ok(()),
+    }
+}
+
+/// A message describing `kind`
+const fn message(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::NoPrefixMatch => "prefixed literal: no prefix match",
+        ErrorKind::NegativeUnsigned => "prefixed literal: negative value for unsigned type",
+        ErrorKind::MisplacedSign => "prefixed literal: misplaced sign",
+        ErrorKind::Separator(SeparatorError::Leading) => "prefixed literal: leading separator",
+        ErrorKind::Separator(SeparatorError::AfterPrefix) => {
+            "prefixed literal: separator after prefix"
+        }
+        ErrorKind::Separator(SeparatorError::Consecutive) => {
+            "prefixed literal: consecutive separators"
+        }
+        ErrorKind::Separator(SeparatorError::Trailing) => "prefixed literal: trailing separator",
+        ErrorKind::Empty => "prefixed literal: no digits",
+        ErrorKind::InvalidDigit => "prefixed literal: invalid digit",
+        ErrorKind::LeadingZero => "prefixed literal: leading zero",
+        ErrorKind::InvalidSuffix => "prefixed literal: invalid suffix",
+        ErrorKind::PosOverflow => "prefixed literal: number too large for type",
+        ErrorKind::NegOverflow => "prefixed literal: number too small for type",
+        ErrorKind::PosWidthOverflow { .. } => "prefixed literal: number too large for field",
+        ErrorKind::NegWidthOverflow { .. } => "prefixed literal: number too small for field",
+        ErrorKind::UnsupportedRadix(_) => "prefixed literal: unsupported radix",
+    }
+}
+
+const fn sign_len(bytes: &[u8]) -> usize {
+    match bytes.first() {
+        Some(b'-' | b'+') => 1,
+        _ => 0,
+    }
+}
+
+/// The length of the longest prefix spelling of `fmt` at `bytes[start..]`
+const fn prefix_len(fmt: &PrefixFmt, bytes: &[u8], start: usize) -> Option<usize> {
+    let mut longest = match affix_at(fmt, bytes, start, fmt.prefix) {
+        true => Some(fmt.prefix.len()),
+        false => None,
+    };
+
+    let mut index = 0;
+    while index < fmt.aliases.len() {
+        let alias = fmt.aliases[index];
+        if affix_at(fmt, bytes, start, alias) {
+            longest = match longest {
+                Some(len) if len >= alias.len() => Some(len),
+                _ => Some(alias.len()),
+            };
+        }
+        index += 1;
+    }
+    longest
+}
+
+/// The start of the longest suffix spelling of `fmt` ending `bytes`, at or after `start`
+const fn suffix_start(fmt: &PrefixFmt, bytes: &[u8], start: usize) -> Option<usize> {
+    let mut longest = None;
+    let mut index = 0;
+    while index <= fmt.suffix_aliases.len() {
+        let suffix = match index {
+            0 => fmt.suffix,
+            _ => fmt.suffix_aliases[index - 1],
+        };
+        let fits = bytes.len() >= start + suffix.len();
+        if fits && affix_at(fmt, bytes, bytes.len() - suffix.len(), suffix) {
+            longest = match longest {
+                Some(len) if len >= suffix.len() => Some(len),
+                _ => Some(suffix.len()),
+            };
+        }
+        index += 1;
+    }
+
+    match longest {
+        Some(len) => Some(bytes.len() - len),
+        None => None,
+    }
+}
+
+/// Returns true if `affix` is spelled at `bytes[at..]`
+const fn affix_at(fmt: &PrefixFmt, bytes: &[u8], at: usize, affix: &str) -> bool {
+    let affix = affix.as_bytes();
+    if bytes.len() < at + affix.len() {
+        return false;
+    }
+
+    let mut index = 0;
+    while index < affix.len() {
+        let (a, b) = (bytes[at + index], affix[index]);
+        match fmt.case_sensitive {
+            true if a != b => return false,
+            false if !a.eq_ignore_ascii_case(&b) => return false,
+            _ => {}
+        }
+        index += 1;
+    }
+    true
+}
+
+const fn is_separator(separators: &Separators, c: char) -> bool {
+    let mut index = 0;
+    while index < separators.chars.len() {
+        if separators.chars[index] == c {
+            return true;
+        }
+        index += 1;
+    }
+    false
+}
+
+const fn digit_value(fmt: &PrefixFmt, c: char) -> Option<u32> {
+    match fmt.alphabet {
+        Some(alphabet) => alphabet.const_digit_value(c),
+        None => c.to_digit(fmt.radix),
+    }
+}
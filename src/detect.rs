@@ -0,0 +1,62 @@
+use crate::PrefixFmt;
+
+/// A parsed value, along with the notation it was written in.
+///
+/// Returned by [`PrefixParse::parse_detect`](crate::PrefixParse::parse_detect), so that edited
+/// values can be written back the way the user wrote them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Detected<'f, T> {
+    /// The parsed value
+    pub value: T,
+    /// The format that matched
+    pub fmt: &'f PrefixFmt<'f>,
+    /// The spelling of the prefix that matched, either the canonical prefix or an alias
+    pub prefix: &'f str,
+    /// The letter case of the prefix as written
+    pub prefix_case: LetterCase,
+    /// The number of digits written, including leading zeros but not separators
+    pub digits: usize,
+    /// The letter case of the digits as written
+    pub digit_case: LetterCase,
+}
+
+/// The case of the ASCII letters in some text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LetterCase {
+    /// No letters, e.g. `0b` prefixes or decimal digits
+    #[default]
+    Uncased,
+    /// All letters lowercase
+    Lower,
+    /// All letters uppercase
+    Upper,
+    /// Both lowercase and uppercase letters
+    Mixed,
+}
+
+impl LetterCase {
+    /// The case of the ASCII letters in `text`
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::LetterCase;
+    ///
+    /// assert_eq!(LetterCase::of("0x"), LetterCase::Lower);
+    /// assert_eq!(LetterCase::of("DEADBEEF"), LetterCase::Upper);
+    /// assert_eq!(LetterCase::of("DeadBeef"), LetterCase::Mixed);
+    /// assert_eq!(LetterCase::of("1234"), LetterCase::Uncased);
+    /// ```
+    pub fn of(text: &str) -> Self {
+        text.chars()
+            .filter(char::is_ascii_alphabetic)
+            .map(|c| match c.is_ascii_uppercase() {
+                true => LetterCase::Upper,
+                false => LetterCase::Lower,
+            })
+            .fold(LetterCase::Uncased, |case, letter| match case {
+                LetterCase::Uncased => letter,
+                case if case == letter => case,
+                _ => LetterCase::Mixed,
+            })
+    }
+}
@@ -0,0 +1,204 @@
+use core::fmt::{self, Write};
+
+use num_traits::PrimInt;
+
+use crate::{Detected, LetterCase, PrefixFmt};
+
+/// Renders an integer in a [`PrefixFmt`], created by [`PrefixFmt::display`].
+///
+/// The output always parses back to the same value with
+/// [`PrefixParse::parse_with`](crate::PrefixParse::parse_with) and the same format, or with
+/// [`PrefixParse::parse_digits_with`](crate::PrefixParse::parse_digits_with) for alphabets of more
+/// than 36 digits.
+///
+/// # Example
+/// ```
+/// use prefix_parse::{PrefixFmt, PrefixParse, Separators, HEX, HEX_SUFFIX};
+///
+/// assert_eq!(HEX.display(255u8).to_string(), "0xff");
+/// assert_eq!(HEX.display(-255i32).uppercase().width(4).to_string(), "-0x00FF");
+///
+/// let rust_hex = PrefixFmt::new("0x", 16)?.with_separators(Separators {
+///     chars: &['_'],
+///     after_prefix: false,
+///     consecutive: false,
+///     trailing: false,
+/// });
+/// let rendered = rust_hex.display(0xDEADBEEFu32).uppercase().group(4).to_string();
+/// assert_eq!(rendered, "0xDEAD_BEEF");
+/// assert_eq!(u32::parse_with(&rust_hex, &rendered), Ok(0xDEADBEEF));
+///
+/// // suffixed numbers starting with a letter digit get a leading zero, as assemblers require
+/// let rendered = HEX_SUFFIX.display(255u32).to_string();
+/// assert_eq!(rendered, "0ffh");
+/// assert_eq!(u32::parse_suffixed(&rendered), Ok(255));
+/// assert_eq!(HEX_SUFFIX.display(0x1Fu32).to_string(), "1fh");
+/// # Ok::<(), prefix_parse::FmtError>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PrefixDisplay<'f, T> {
+    fmt: &'f PrefixFmt<'f>,
+    value: T,
+    prefix: &'f str,
+    prefix_case: LetterCase,
+    digit_case: LetterCase,
+    width: usize,
+    group: usize,
+}
+
+impl<'f, T: PrimInt> PrefixDisplay<'f, T> {
+    pub(crate) fn new(fmt: &'f PrefixFmt<'f>, value: T) -> Self {
+        PrefixDisplay {
+            fmt,
+            value,
+            prefix: fmt.prefix(),
+            prefix_case: LetterCase::Uncased,
+            digit_case: LetterCase::Lower,
+            width: 0,
+            group: 0,
+        }
+    }
+
+    /// Render letter digits in uppercase
+    pub fn uppercase(self) -> Self {
+        self.digit_case(LetterCase::Upper)
+    }
+
+    /// Render letter digits in lowercase, the default
+    pub fn lowercase(self) -> Self {
+        self.digit_case(LetterCase::Lower)
+    }
+
+    /// Render letter digits in `case`, where `Upper` gives uppercase and anything else lowercase
+    ///
+    /// Has no effect on case-sensitive alphabets, whose digits are rendered as defined.
+    pub fn digit_case(self, digit_case: LetterCase) -> Self {
+        PrefixDisplay { digit_case, ..self }
+    }
+
+    /// Render the prefix and suffix in `case`, where `Uncased` and `Mixed` keep the format's
+    /// spelling
+    ///
+    /// Has no effect on case-sensitive formats, whose affixes are rendered as defined.
+    pub fn prefix_case(self, prefix_case: LetterCase) -> Self {
+        PrefixDisplay {
+            prefix_case,
+            ..self
+        }
+    }
+
+    /// Pad the digits with leading zeros to at least `width` digits
+    pub fn width(self, width: usize) -> Self {
+        PrefixDisplay { width, ..self }
+    }
+
+    /// Separate the digits into groups of `group`, counting from the least significant digit
+    ///
+    /// Groups are separated by the first of the format's separators, and have no effect if the
+    /// format accepts no separators.
+    pub fn group(self, group: usize) -> Self {
+        PrefixDisplay { group, ..self }
+    }
+
+    fn write_affix(&self, f: &mut fmt::Formatter<'_>, affix: &str) -> fmt::Result {
+        match (self.fmt.is_case_sensitive(), self.prefix_case) {
+            (false, LetterCase::Upper) => affix
+                .chars()
+                .try_for_each(|c| f.write_char(c.to_ascii_uppercase())),
+            (false, LetterCase::Lower) => affix
+                .chars()
+                .try_for_each(|c| f.write_char(c.to_ascii_lowercase())),
+            _ => f.write_str(affix),
+        }
+    }
+
+    fn digit(&self, value: u32) -> char {
+        let digit = match self.fmt.alphabet() {
+            Some(alphabet) if !alphabet.is_case_insensitive() => alphabet.digit(value),
+            Some(alphabet) => alphabet.digit(value).map(|c| self.cased(c)),
+            None => char::from_digit(value, self.fmt.radix()).map(|c| self.cased(c)),
+        };
+        digit.unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
+    fn cased(&self, digit: char) -> char {
+        match self.digit_case {
+            LetterCase::Upper => digit.to_ascii_uppercase(),
+            _ => digit.to_ascii_lowercase(),
+        }
+    }
+}
+
+impl<T: PrimInt> fmt::Display for PrefixDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (negative, mut magnitude) = match self.value.to_i128() {
+            Some(value) => (value < 0, value.unsigned_abs()),
+            None => (false, self.value.to_u128().unwrap_or_default()),
+        };
+
+        // digit values, least significant first; a u128 has at most 128 digits in radix 2
+        let radix = u128::from(self.fmt.radix());
+        let mut digits = [0u8; 128];
+        let mut len = 0;
+        while magnitude > 0 || len == 0 {
+            digits[len] = (magnitude % radix) as u8;
+            magnitude /= radix;
+            len += 1;
+        }
+
+        if negative {
+            f.write_char('-')?;
+        }
+        self.write_affix(f, self.prefix)?;
+
+        let separator = self
+            .fmt
+            .separators()
+            .chars
+            .first()
+            .filter(|_| self.group > 0);
+        let width = self.width.max(len);
+        // assemblers read a suffixed number starting with a letter as a name, as in `ffh`
+        let leading = self.digit(digits.get(width - 1).copied().map_or(0, u32::from));
+        if self.fmt.prefix().is_empty()
+            && !self.fmt.suffix().is_empty()
+            && self.fmt.alphabet().is_none()
+            && !leading.is_ascii_digit()
+        {
+            f.write_char('0')?;
+        }
+        for position in (0..width).rev() {
+            f.write_char(self.digit(digits.get(position).copied().map_or(0, u32::from)))?;
+            if let Some(&separator) = separator
+                && position > 0
+                && position % self.group == 0
+            {
+                f.write_char(separator)?;
+            }
+        }
+
+        self.write_affix(f, self.fmt.suffix())
+    }
+}
+
+impl<'f, T: PrimInt> Detected<'f, T> {
+    /// Render the value in the notation it was written in
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::PrefixParse;
+    ///
+    /// let detected = u32::parse_detect("0X00ff").unwrap();
+    /// let edited = prefix_parse::Detected { value: 0x1f, ..detected };
+    /// assert_eq!(edited.display().to_string(), "0X001f");
+    /// ```
+    pub fn display(&self) -> PrefixDisplay<'f, T> {
+        PrefixDisplay {
+            prefix: self.prefix,
+            ..PrefixDisplay::new(self.fmt, self.value)
+        }
+        .prefix_case(self.prefix_case)
+        .digit_case(self.digit_case)
+        .width(self.digits)
+    }
+}
@@ -0,0 +1,206 @@
+use core::fmt;
+
+use num_traits::Num;
+
+use crate::PrefixFmt;
+
+/// Error type for `PrefixParse`
+///
+/// Records what went wrong, where in the source it went wrong, and which format was assumed.
+///
+/// # Example
+/// ```
+/// use prefix_parse::{ErrorKind, PrefixParse, HEX};
+///
+/// let error = u32::parse("0x1G").unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::InvalidDigit);
+/// assert_eq!(error.offset(), 3);
+/// assert_eq!(error.found(), Some('G'));
+/// assert_eq!(error.fmt(), Some(&HEX));
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid Digit Found In String: 'G' At Byte 3, Assuming Radix 16"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<'f, T: Num> {
+    kind: ErrorKind,
+    offset: usize,
+    found: Option<char>,
+    fmt: Option<PrefixFmt<'f>>,
+    source: Option<T::FromStrRadixErr>,
+}
+
+impl<'f, T: Num> ParseError<'f, T> {
+    /// An error of `kind` at byte `offset` of the source, caused by `found`
+    pub(crate) fn at(kind: ErrorKind, offset: usize, found: Option<char>) -> Self {
+        ParseError {
+            kind,
+            offset,
+            found,
+            fmt: None,
+            source: None,
+        }
+    }
+
+    /// Records the format that was assumed
+    pub(crate) fn with_fmt(self, fmt: &PrefixFmt<'f>) -> Self {
+        ParseError {
+            fmt: Some(*fmt),
+            ..self
+        }
+    }
+
+    /// Records the error returned by `from_str_radix`
+    pub(crate) fn with_source(self, source: T::FromStrRadixErr) -> Self {
+        ParseError {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// Moves the offset `by` bytes later, for errors found in a tail of the source
+    pub(crate) fn offset_by(self, by: usize) -> Self {
+        ParseError {
+            offset: self.offset + by,
+            ..self
+        }
+    }
+
+    /// What went wrong
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The byte offset in the source of the offending character, or of where the missing part
+    /// was expected
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The offending character, if there is one
+    pub fn found(&self) -> Option<char> {
+        self.found
+    }
+
+    /// The format that was assumed, if a format was chosen
+    pub fn fmt(&self) -> Option<&PrefixFmt<'f>> {
+        self.fmt.as_ref()
+    }
+
+    /// The error returned by `from_str_radix`, if parsing got that far
+    pub fn radix_error(&self) -> Option<&T::FromStrRadixErr> {
+        self.source.as_ref()
+    }
+}
+
+impl<T: Num> fmt::Display for ParseError<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(found) = self.found {
+            write!(f, ": {found:?}")?;
+        }
+        write!(f, " At Byte {}", self.offset)?;
+        if let Some(fmt) = self.fmt {
+            write!(f, ", Assuming Radix {}", fmt.radix())?;
+        }
+        // state the bound in the notation of the input
+        match (self.kind, self.fmt) {
+            (ErrorKind::PosWidthOverflow { bits, signed }, fmt) => {
+                let max = u128::MAX >> (128 - bits + u32::from(signed));
+                match fmt {
+                    Some(fmt) => write!(f, ", Max {}", fmt.display(max)),
+                    None => write!(f, ", Max {max}"),
+                }
+            }
+            (ErrorKind::NegWidthOverflow { bits }, fmt) => {
+                let min = -1i128 << (bits - 1);
+                match fmt {
+                    Some(fmt) => write!(f, ", Min {}", fmt.display(min)),
+                    None => write!(f, ", Min {min}"),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<T> core::error::Error for ParseError<'_, T>
+where
+    T: Num + fmt::Debug,
+    T::FromStrRadixErr: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source as &(dyn core::error::Error + 'static))
+    }
+}
+
+/// The kind of a [`ParseError`], independent of the parsed type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ErrorKind {
+    #[error("No Prefix Match")]
+    NoPrefixMatch,
+    #[error("Negative Value For Unsigned Type")]
+    NegativeUnsigned,
+    #[error("Misplaced Sign")]
+    MisplacedSign,
+    #[error(transparent)]
+    Separator(#[from] SeparatorError),
+    #[error("Cannot Parse Integer From Empty String")]
+    Empty,
+    #[error("Invalid Digit Found In String")]
+    InvalidDigit,
+    #[error("Leading Zero In Non-Zero Decimal Number")]
+    LeadingZero,
+    #[error("Invalid Suffix")]
+    InvalidSuffix,
+    #[error("Number Too Large To Fit In Target Type")]
+    PosOverflow,
+    #[error("Number Too Small To Fit In Target Type")]
+    NegOverflow,
+    /// The value is above the largest value of a field of `bits` bits, signed or unsigned
+    #[error("Number Too Large To Fit In {bits} Bits")]
+    PosWidthOverflow { bits: u32, signed: bool },
+    /// The value is below the smallest value of a signed field of `bits` bits
+    #[error("Number Too Small To Fit In {bits} Bits")]
+    NegWidthOverflow { bits: u32 },
+    #[error("Radix {0} Above 36 Requires parse_digits_with")]
+    UnsupportedRadix(u32),
+}
+
+/// Error type for `PrefixFmt` construction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum FmtError {
+    #[error("Invalid Radix {0}, Expected 2..=36")]
+    InvalidRadix(u32),
+    #[error("No Prefix Or Suffix For Non-Decimal Radix")]
+    AmbiguousPrefix,
+    #[error("Alphabet Has {0} Digits, Expected 2..=256")]
+    AlphabetSize(usize),
+    #[error("Duplicate Digit {0:?} In Alphabet")]
+    DuplicateDigit(char),
+    #[error("Invalid Alias {0:?} In Alphabet")]
+    InvalidAlias(char),
+}
+
+/// Error type for `PrefixSet` construction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SetError {
+    #[error("Formats {0} And {1} Share A Prefix And Suffix")]
+    Conflict(usize, usize),
+}
+
+/// Separator placement rule violated while parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SeparatorError {
+    #[error("Leading Separator")]
+    Leading,
+    #[error("Separator After Prefix")]
+    AfterPrefix,
+    #[error("Consecutive Separators")]
+    Consecutive,
+    #[error("Trailing Separator")]
+    Trailing,
+}
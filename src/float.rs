@@ -0,0 +1,325 @@
+use core::any::TypeId;
+use core::ops::Neg;
+
+use num_traits::Num;
+
+use crate::{ErrorKind, ParseError, Parts, PrefixFmt, split_sign, type_id};
+
+/// An IEEE-754 binary floating-point type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Float {
+    F32,
+    F64,
+}
+
+impl Float {
+    /// The format of `T`, if it is an `f32` or `f64`
+    fn of<T: ?Sized>() -> Option<Self> {
+        match type_id::<T>() {
+            id if id == TypeId::of::<f32>() => Some(Float::F32),
+            id if id == TypeId::of::<f64>() => Some(Float::F64),
+            _ => None,
+        }
+    }
+
+    /// The bits of the significand, including the implicit leading bit
+    const fn precision(self) -> i64 {
+        match self {
+            Float::F32 => 24,
+            Float::F64 => 53,
+        }
+    }
+
+    /// The exponent of the least significant bit of the smallest subnormal
+    const fn min_lsb(self) -> i64 {
+        match self {
+            Float::F32 => -149,
+            Float::F64 => -1074,
+        }
+    }
+
+    /// The exponent of the smallest power of two too large to represent
+    const fn max_exponent(self) -> i64 {
+        match self {
+            Float::F32 => 128,
+            Float::F64 => 1024,
+        }
+    }
+
+    /// The bits of the float nearest `significand`, rounding ties to even
+    fn round(self, significand: Significand) -> u64 {
+        let Significand {
+            bits,
+            exponent,
+            sticky,
+        } = significand;
+        if bits == 0 {
+            return 0;
+        }
+
+        // the exponent of the least significant bit kept, limited by the subnormals
+        let top = exponent + i64::from(u64::BITS - bits.leading_zeros()) - 1;
+        let mut lsb = (top - (self.precision() - 1)).max(self.min_lsb());
+        let shift = lsb - exponent;
+        let mut kept = match shift {
+            ..=0 => bits << -shift,
+            // everything dropped is below half the least significant bit
+            66.. => 0,
+            _ => {
+                let wide = u128::from(bits);
+                let kept = (wide >> shift) as u64;
+                let dropped = wide & ((1 << shift) - 1);
+                let half = 1 << (shift - 1);
+                let round_up = dropped > half || (dropped == half && (sticky || kept & 1 == 1));
+                kept + u64::from(round_up)
+            }
+        };
+
+        // rounding up carried into a new bit
+        if kept >> self.precision() != 0 {
+            kept >>= 1;
+            lsb += 1;
+        }
+
+        let fraction_bits = self.precision() - 1;
+        let bias = self.max_exponent() - 1;
+        let top = lsb + i64::from(u64::BITS - kept.leading_zeros()) - 1;
+        if kept != 0 && top >= self.max_exponent() {
+            // infinity, with every exponent bit set
+            return ((2 * bias + 1) as u64) << fraction_bits;
+        }
+
+        match kept >> fraction_bits {
+            // subnormal, whose exponent bits are zero
+            0 => kept,
+            _ => {
+                let biased = (lsb + fraction_bits + bias) as u64;
+                biased << fraction_bits | kept & ((1 << fraction_bits) - 1)
+            }
+        }
+    }
+}
+
+/// Parses the digits of `parts` as a hexadecimal float, like C's `0x1.8p3`, or a NaN with a C
+/// payload, like `nan(0x1)`, if `T` is an `f32` or `f64`
+///
+/// The value is rounded to nearest, ties to even, as C's `strtod` does.
+pub(crate) fn parse<'f, T: Num>(
+    parts: &Parts,
+    fmt: &PrefixFmt<'f>,
+) -> Option<Result<T, ParseError<'f, T>>> {
+    let float = Float::of::<T>()?;
+    let bits = match is_nan_payload(parts.digits) {
+        true => None,
+        false => match hex_significand(parts, fmt) {
+            Ok(significand) => Some(float.round(significand)),
+            Err(error) => return Some(Err(error)),
+        },
+    };
+
+    let value = match float {
+        Float::F32 => cast(signed(
+            parts.negative,
+            bits.map_or(f32::NAN, |bits| f32::from_bits(bits as u32)),
+        )),
+        Float::F64 => cast(signed(
+            parts.negative,
+            bits.map_or(f64::NAN, f64::from_bits),
+        )),
+    };
+    value.map(Ok)
+}
+
+/// Negates `value` if `negative`
+fn signed<F: Neg<Output = F>>(negative: bool, value: F) -> F {
+    match negative {
+        true => -value,
+        false => value,
+    }
+}
+
+/// Moves the primitive float `value` into a `T`, if it is one
+fn cast<T, F: Copy + 'static>(value: F) -> Option<T> {
+    // SAFETY: `T` is `F`, as a primitive float has no lifetimes that `type_id` could have erased
+    (type_id::<T>() == TypeId::of::<F>()).then(|| unsafe { core::mem::transmute_copy(&value) })
+}
+
+/// A float as `bits * 2^exponent`, with `sticky` set if nonzero bits below were dropped
+struct Significand {
+    bits: u64,
+    exponent: i64,
+    sticky: bool,
+}
+
+/// Reads the digits of `parts` as hexadecimal digits, with an optional point and an optional
+/// binary exponent introduced by `p`
+fn hex_significand<'f, T: Num>(
+    parts: &Parts,
+    fmt: &PrefixFmt<'f>,
+) -> Result<Significand, ParseError<'f, T>> {
+    let start = parts.digits_offset();
+    let error = |kind, index: usize| {
+        let offset = start + index;
+        ParseError::at(kind, offset, parts.char_at(offset)).with_fmt(fmt)
+    };
+
+    let mut significand = Significand {
+        bits: 0,
+        exponent: 0,
+        sticky: false,
+    };
+    let (mut digits, mut point, mut exponent_at) = (0, false, None);
+    for (index, c) in parts.digits.char_indices() {
+        match c {
+            _ if fmt.separators.chars.contains(&c) => continue,
+            '.' if !point => {
+                point = true;
+                continue;
+            }
+            'p' | 'P' => {
+                exponent_at = Some(index);
+                break;
+            }
+            _ => {}
+        }
+
+        let digit = c
+            .to_digit(16)
+            .ok_or_else(|| error(ErrorKind::InvalidDigit, index))?;
+        digits += 1;
+        // keep 64 bits at most, which is more than any float holds
+        if significand.bits >> 60 == 0 {
+            significand.bits = significand.bits << 4 | u64::from(digit);
+            significand.exponent -= if point { 4 } else { 0 };
+        } else {
+            significand.sticky |= digit != 0;
+            significand.exponent += if point { 0 } else { 4 };
+        }
+    }
+
+    if digits == 0 {
+        return Err(ParseError::at(ErrorKind::Empty, start, None).with_fmt(fmt));
+    }
+
+    if let Some(at) = exponent_at {
+        let src = &parts.digits[at + 1..];
+        let (negative, magnitude) = split_sign(src);
+        let offset = at + 1 + src.len() - magnitude.len();
+        if magnitude.is_empty() {
+            return Err(error(ErrorKind::InvalidDigit, at));
+        }
+
+        let mut exponent: i64 = 0;
+        for (index, c) in magnitude.char_indices() {
+            if fmt.separators.chars.contains(&c) {
+                continue;
+            }
+            let digit = c
+                .to_digit(10)
+                .ok_or_else(|| error(ErrorKind::InvalidDigit, offset + index))?;
+            // far beyond the range of any float, so saturating changes nothing
+            exponent = (exponent * 10 + i64::from(digit)).min(1 << 20);
+        }
+        significand.exponent += match negative {
+            true => -exponent,
+            false => exponent,
+        };
+    }
+
+    Ok(significand)
+}
+
+/// Returns true for a NaN with a C payload, such as `nan(0x1)` or `NAN()`
+pub(crate) fn is_nan_payload(digits: &str) -> bool {
+    let Some(payload) = digits
+        .get(..4)
+        .filter(|head| head.eq_ignore_ascii_case("nan("))
+        .and_then(|_| digits[4..].strip_suffix(')'))
+    else {
+        return false;
+    };
+    payload
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PrefixParse;
+
+    /// Each input, and the bits of the nearest `f32` and `f64`
+    #[rustfmt::skip]
+    const ROUNDING: &[(&str, u32, u64)] = &[
+        ("0x1.8p3", 0x4140_0000, 0x4028_0000_0000_0000),
+        ("-0x1p0", 0xbf80_0000, 0xbff0_0000_0000_0000),
+        ("0x0p0", 0x0000_0000, 0x0000_0000_0000_0000),
+
+        // ties to even, down from an even significand and up from an odd one
+        ("0x1.000001p0", 0x3f80_0000, 0x3ff0_0000_1000_0000),
+        ("0x1.000003p0", 0x3f80_0002, 0x3ff0_0000_3000_0000),
+        ("0x1.00000000000008p0", 0x3f80_0000, 0x3ff0_0000_0000_0000),
+        ("0x1.00000000000018p0", 0x3f80_0000, 0x3ff0_0000_0000_0002),
+
+        // a nonzero bit far below the halfway bit breaks the tie upwards
+        ("0x1.0000010000000001p0", 0x3f80_0001, 0x3ff0_0000_1000_0000),
+        ("0x1.000000000000080000000000000001p0", 0x3f80_0000, 0x3ff0_0000_0000_0001),
+
+        // the smallest subnormal, and the ties with zero on either side of it
+        ("0x1p-149", 0x0000_0001, 0x36a0_0000_0000_0000),
+        ("0x1p-150", 0x0000_0000, 0x3690_0000_0000_0000),
+        ("0x1.000001p-150", 0x0000_0001, 0x3690_0000_1000_0000),
+        ("0x1p-1074", 0x0000_0000, 0x0000_0000_0000_0001),
+        ("0x1p-1075", 0x0000_0000, 0x0000_0000_0000_0000),
+        ("0x1.0000000000001p-1075", 0x0000_0000, 0x0000_0000_0000_0001),
+        ("-0x1p-1075", 0x8000_0000, 0x8000_0000_0000_0000),
+
+        // rounding up carries into a new exponent, from subnormal to normal and between normals
+        ("0x0.fffffep-126", 0x007f_ffff, 0x380f_ffff_c000_0000),
+        ("0x0.ffffffp-126", 0x0080_0000, 0x380f_ffff_e000_0000),
+        ("0x0.fffffffffffff8p-1022", 0x0000_0000, 0x0010_0000_0000_0000),
+        ("0x1.ffffffp0", 0x4000_0000, 0x3fff_ffff_f000_0000),
+        ("0x1.fffffffffffff8p0", 0x4000_0000, 0x4000_0000_0000_0000),
+
+        // the largest finite values, and rounding up past them to infinity
+        ("0x1.fffffep127", 0x7f7f_ffff, 0x47ef_ffff_e000_0000),
+        ("0x1.fffffefp127", 0x7f7f_ffff, 0x47ef_ffff_ef00_0000),
+        ("0x1.ffffffp127", 0x7f80_0000, 0x47ef_ffff_f000_0000),
+        ("0x1p128", 0x7f80_0000, 0x47f0_0000_0000_0000),
+        ("0x1.fffffffffffff7p1023", 0x7f80_0000, 0x7fef_ffff_ffff_ffff),
+        ("0x1.fffffffffffff8p1023", 0x7f80_0000, 0x7ff0_0000_0000_0000),
+        ("0x1p1024", 0x7f80_0000, 0x7ff0_0000_0000_0000),
+    ];
+
+    #[test]
+    fn rounding() {
+        for &(src, f32_bits, f64_bits) in ROUNDING {
+            assert_eq!(
+                f32::parse(src).map(f32::to_bits).ok(),
+                Some(f32_bits),
+                "f32 {src}"
+            );
+            assert_eq!(
+                f64::parse(src).map(f64::to_bits).ok(),
+                Some(f64_bits),
+                "f64 {src}"
+            );
+        }
+    }
+
+    #[test]
+    fn nan_payload() {
+        for src in ["nan(0x1)", "NAN()", "-nan(abc)"] {
+            let negative = src.starts_with('-');
+            let single = f32::parse(src).unwrap();
+            let double = f64::parse(src).unwrap();
+            assert!(
+                single.is_nan() && single.is_sign_negative() == negative,
+                "f32 {src}"
+            );
+            assert!(
+                double.is_nan() && double.is_sign_negative() == negative,
+                "f64 {src}"
+            );
+        }
+    }
+}
@@ -1,105 +1,1340 @@
-use num_traits::Num;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::any::TypeId;
+use core::fmt::Write;
+use core::marker::PhantomData;
+
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, FromPrimitive, Num, PrimInt};
 use tap::Pipe;
 
+mod alphabet;
+mod buffer;
+pub mod c;
+mod const_parse;
+mod detect;
+mod display;
+mod error;
+mod float;
+mod options;
+mod prefixed;
+pub mod python;
+pub mod rust;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod set;
+#[cfg(feature = "alloc")]
+mod trie;
+#[cfg(feature = "clap")]
+mod value_parser;
+
+pub use alphabet::Alphabet;
+use buffer::DigitBuf;
+pub use const_parse::*;
+pub use detect::{Detected, LetterCase};
+pub use display::PrefixDisplay;
+pub use error::{ErrorKind, FmtError, ParseError, SeparatorError, SetError};
+pub use options::{Overflow, ParseOptions};
+#[cfg(feature = "macros")]
+pub use prefix_parse_macros::prefixed;
+pub use prefixed::Prefixed;
+pub use set::{PrefixMatcher, PrefixSet};
+#[cfg(feature = "alloc")]
+pub use trie::PrefixTrie;
+#[cfg(feature = "clap")]
+pub use value_parser::PrefixValueParser;
+
 /// Defines a prefix format.
-#[derive(Debug)]
+///
+/// Formats are built with [`PrefixFmt::new`], which validates the radix, so parsing with any
+/// `PrefixFmt` is panic-free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PrefixFmt<'a> {
-    pub prefix: &'a str,
-    pub radix: u32,
+    prefix: &'a str,
+    aliases: &'a [&'a str],
+    suffix: &'a str,
+    suffix_aliases: &'a [&'a str],
+    case_sensitive: bool,
+    separators: Separators<'a>,
+    radix: u32,
+    alphabet: Option<&'a Alphabet<'a>>,
 }
 
-/// '0x' prefix for hexadecimal numbers
-pub const HEX: PrefixFmt = PrefixFmt {
-    prefix: "0x",
-    radix: 16,
-};
-/// '0o' prefix for octal numbers
-pub const OCT: PrefixFmt = PrefixFmt {
-    prefix: "0o",
-    radix: 8,
-};
+impl<'a> PrefixFmt<'a> {
+    /// Create a new case-sensitive format without aliases or separators
+    ///
+    /// # Errors
+    /// - [`FmtError::InvalidRadix`] if `radix` is outside `2..=36`.
+    /// - [`FmtError::AmbiguousPrefix`] if `prefix` is empty and `radix` is not 10, since such a
+    ///   format cannot be told apart from decimal. Use [`PrefixFmt::suffixed`] for formats marked
+    ///   by a suffix instead.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{FmtError, PrefixFmt};
+    ///
+    /// const BASE36: PrefixFmt = match PrefixFmt::new("0z", 36) {
+    ///     Ok(fmt) => fmt,
+    ///     Err(_) => panic!("invalid format"),
+    /// };
+    /// assert_eq!(BASE36.radix(), 36);
+    ///
+    /// assert_eq!(PrefixFmt::new("0q", 40), Err(FmtError::InvalidRadix(40)));
+    /// assert_eq!(PrefixFmt::new("", 16), Err(FmtError::AmbiguousPrefix));
+    /// ```
+    pub const fn new(prefix: &'a str, radix: u32) -> Result<Self, FmtError> {
+        PrefixFmt::affixed(prefix, "", radix)
+    }
 
-/// '0b' prefix for binary numbers
-pub const BIN: PrefixFmt = PrefixFmt {
-    prefix: "0b",
-    radix: 2,
-};
+    /// Create a new case-sensitive format marked by a suffix rather than a prefix
+    ///
+    /// # Errors
+    /// - [`FmtError::InvalidRadix`] if `radix` is outside `2..=36`.
+    /// - [`FmtError::AmbiguousPrefix`] if `suffix` is empty and `radix` is not 10.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{PrefixFmt, PrefixParse};
+    ///
+    /// let hex = PrefixFmt::suffixed("h", 16)?;
+    /// assert_eq!(u32::parse_with(&hex, "0FFh"), Ok(255));
+    /// # Ok::<(), prefix_parse::FmtError>(())
+    /// ```
+    pub const fn suffixed(suffix: &'a str, radix: u32) -> Result<Self, FmtError> {
+        PrefixFmt::affixed("", suffix, radix)
+    }
+
+    const fn affixed(prefix: &'a str, suffix: &'a str, radix: u32) -> Result<Self, FmtError> {
+        if radix < 2 || radix > 36 {
+            return Err(FmtError::InvalidRadix(radix));
+        }
+        if prefix.is_empty() && suffix.is_empty() && radix != 10 {
+            return Err(FmtError::AmbiguousPrefix);
+        }
+
+        Ok(PrefixFmt {
+            prefix,
+            aliases: &[],
+            suffix,
+            suffix_aliases: &[],
+            case_sensitive: true,
+            separators: Separators::NONE,
+            radix,
+            alphabet: None,
+        })
+    }
+
+    /// Create a new case-sensitive format whose digits are drawn from `alphabet`
+    ///
+    /// The radix is the size of the alphabet, which may be up to 256. Unlike [`PrefixFmt::new`],
+    /// the prefix may be empty, as alphabet-encoded values are usually unprefixed.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{Alphabet, PrefixFmt, PrefixParse};
+    ///
+    /// const BASE58: PrefixFmt = PrefixFmt::from_alphabet("", &Alphabet::BASE58);
+    /// assert_eq!(BASE58.radix(), 58);
+    /// assert_eq!(u64::parse_digits_with(&BASE58, "jpXCZedGfVQ"), Ok(u64::MAX));
+    /// ```
+    pub const fn from_alphabet(prefix: &'a str, alphabet: &'a Alphabet<'a>) -> Self {
+        PrefixFmt {
+            prefix,
+            aliases: &[],
+            case_sensitive: true,
+            separators: Separators::NONE,
+            suffix: "",
+            suffix_aliases: &[],
+            radix: alphabet.radix(),
+            alphabet: Some(alphabet),
+        }
+    }
+
+    /// Add alternative spellings of the prefix, e.g. `#` or `$` for hexadecimal
+    ///
+    /// # Errors
+    /// [`FmtError::AmbiguousPrefix`] if an alias is empty and the radix is not 10.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::PrefixFmt;
+    ///
+    /// let vb_hex = PrefixFmt::new("&H", 16)?.with_aliases(&["#", "$"])?;
+    /// assert_eq!(vb_hex.strip_prefix("$FF"), Some("FF"));
+    /// # Ok::<(), prefix_parse::FmtError>(())
+    /// ```
+    pub const fn with_aliases(self, aliases: &'a [&'a str]) -> Result<Self, FmtError> {
+        let mut index = 0;
+        while index < aliases.len() {
+            if aliases[index].is_empty() && self.radix != 10 {
+                return Err(FmtError::AmbiguousPrefix);
+            }
+            index += 1;
+        }
+
+        Ok(PrefixFmt { aliases, ..self })
+    }
+
+    /// Require a suffix after the digits, in addition to the prefix
+    pub const fn with_suffix(self, suffix: &'a str) -> Self {
+        PrefixFmt { suffix, ..self }
+    }
+
+    /// Add alternative spellings of the suffix, e.g. `q` for an octal `o` suffix
+    ///
+    /// # Errors
+    /// [`FmtError::AmbiguousPrefix`] if an alias is empty and the format has no prefix.
+    pub const fn with_suffix_aliases(
+        self,
+        suffix_aliases: &'a [&'a str],
+    ) -> Result<Self, FmtError> {
+        let mut index = 0;
+        while index < suffix_aliases.len() {
+            if suffix_aliases[index].is_empty() && self.prefix.is_empty() {
+                return Err(FmtError::AmbiguousPrefix);
+            }
+            index += 1;
+        }
+
+        Ok(PrefixFmt {
+            suffix_aliases,
+            ..self
+        })
+    }
+
+    /// Match the prefix, suffix and their aliases regardless of ASCII case
+    pub const fn case_insensitive(self) -> Self {
+        PrefixFmt {
+            case_sensitive: false,
+            ..self
+        }
+    }
+
+    /// Accept digit separators following the prefix
+    pub const fn with_separators(self, separators: Separators<'a>) -> Self {
+        PrefixFmt { separators, ..self }
+    }
+
+    /// The canonical spelling of the prefix
+    pub const fn prefix(&self) -> &'a str {
+        self.prefix
+    }
+
+    /// Alternative spellings of the prefix
+    pub const fn aliases(&self) -> &'a [&'a str] {
+        self.aliases
+    }
+
+    /// The canonical spelling of the suffix, empty if the format has none
+    pub const fn suffix(&self) -> &'a str {
+        self.suffix
+    }
+
+    /// Alternative spellings of the suffix
+    pub const fn suffix_aliases(&self) -> &'a [&'a str] {
+        self.suffix_aliases
+    }
+
+    /// If false, the prefix, suffix and their aliases match regardless of ASCII case
+    pub const fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Digit separators accepted in the digits following the prefix
+    pub const fn separators(&self) -> Separators<'a> {
+        self.separators
+    }
+
+    /// The radix of the digits following the prefix
+    pub const fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// The digit alphabet, if the format does not use the standard `0-9a-z` digits
+    pub const fn alphabet(&self) -> Option<&'a Alphabet<'a>> {
+        self.alphabet
+    }
+
+    /// The value of the digit `c` in this format, or `None` if `c` is not a digit
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{Alphabet, PrefixFmt, HEX};
+    ///
+    /// assert_eq!(HEX.digit_value('F'), Some(15));
+    /// assert_eq!(HEX.digit_value('g'), None);
+    ///
+    /// let crockford = PrefixFmt::from_alphabet("", &Alphabet::CROCKFORD);
+    /// assert_eq!(crockford.digit_value('o'), Some(0));
+    /// ```
+    pub fn digit_value(&self, c: char) -> Option<u32> {
+        match self.alphabet {
+            Some(alphabet) => alphabet.digit_value(c),
+            None => c.to_digit(self.radix),
+        }
+    }
+
+    /// Render `value` in this format, see [`PrefixDisplay`] for rendering options
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{HEX, HEX_SUFFIX};
+    ///
+    /// assert_eq!(format!("{}", HEX.display(255u32)), "0xff");
+    /// assert_eq!(format!("{}", HEX_SUFFIX.display(255u32).uppercase().width(3)), "0FFh");
+    /// ```
+    pub fn display<T: PrimInt>(&'a self, value: T) -> PrefixDisplay<'a, T> {
+        PrefixDisplay::new(self, value)
+    }
+
+    /// Render `value` in this format as a string
+    ///
+    /// The result parses back to `value` with [`PrefixParse::parse_with`] and this format.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{PrefixFmt, PrefixParse};
+    ///
+    /// let base36 = PrefixFmt::new("0z", 36)?;
+    /// assert_eq!(base36.to_prefixed_string(2015u32), "0z1jz");
+    /// assert_eq!(u32::parse_with(&base36, &base36.to_prefixed_string(2015u32)), Ok(2015));
+    /// # Ok::<(), prefix_parse::FmtError>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_prefixed_string<T: PrimInt>(&'a self, value: T) -> alloc::string::String {
+        alloc::string::ToString::to_string(&self.display(value))
+    }
+
+    /// Strips the prefix, or the longest matching alias, from the front of `src`
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{PrefixFmt, HEX};
+    ///
+    /// assert_eq!(HEX.strip_prefix("0XFF"), Some("FF"));
+    ///
+    /// let vb_hex = PrefixFmt::new("&H", 16)?.with_aliases(&["#", "$"])?;
+    /// assert_eq!(vb_hex.strip_prefix("$FF"), Some("FF"));
+    /// assert_eq!(vb_hex.strip_prefix("&hFF"), None);
+    /// # Ok::<(), prefix_parse::FmtError>(())
+    /// ```
+    pub fn strip_prefix<'s>(&self, src: &'s str) -> Option<&'s str> {
+        self.match_prefix(src)
+            .map(|spelling| &src[spelling.len()..])
+    }
+
+    /// Returns the longest spelling of the prefix that `src` starts with
+    fn match_prefix(&self, src: &str) -> Option<&'a str> {
+        longest(self.prefix, self.aliases, |spelling| {
+            self.starts_with(src, spelling)
+        })
+    }
+
+    /// Strips the suffix, or the longest matching suffix alias, from the end of `src`
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::HEX_SUFFIX;
+    ///
+    /// assert_eq!(HEX_SUFFIX.strip_suffix("0FFH"), Some("0FF"));
+    /// assert_eq!(HEX_SUFFIX.strip_suffix("0FF"), None);
+    /// ```
+    pub fn strip_suffix<'s>(&self, src: &'s str) -> Option<&'s str> {
+        longest(self.suffix, self.suffix_aliases, |spelling| {
+            self.ends_with(src, spelling)
+        })
+        .map(|spelling| &src[..src.len() - spelling.len()])
+    }
+
+    /// Splits `src` into its sign, affixes and digits, if the affixes match
+    fn split<'s>(&self, src: &'s str) -> Option<Parts<'s>> {
+        let (negative, unsigned) = split_sign(src);
+        let (prefix, rest) = unsigned.split_at(self.match_prefix(unsigned)?.len());
+
+        Some(Parts {
+            src,
+            negative,
+            prefix,
+            digits: self.strip_suffix(rest)?,
+        })
+    }
+
+    /// Splits the longest numeric literal from the front of `src` into its sign, affixes and
+    /// digits, returning them with the unconsumed remainder, if the affixes match
+    fn split_partial<'s>(&self, src: &'s str) -> Option<(Parts<'s>, &'s str)> {
+        let (negative, unsigned) = split_sign(src);
+        let (prefix, rest) = unsigned.split_at(self.match_prefix(unsigned)?.len());
+        let (digits, rest) = rest.split_at(self.digits_len(rest, !prefix.is_empty()));
+
+        let suffix = longest(self.suffix, self.suffix_aliases, |spelling| {
+            self.starts_with(rest, spelling)
+        })?;
+
+        let parts = Parts {
+            src,
+            negative,
+            prefix,
+            digits,
+        };
+        Some((parts, &rest[suffix.len()..]))
+    }
+
+    /// The byte length of the run of digits and well-placed separators at the front of `src`
+    fn digits_len(&self, src: &str, prefixed: bool) -> usize {
+        let separators = &self.separators;
+        let mut len = 0;
+        let mut prev_separator = false;
+        for (index, c) in src.char_indices() {
+            let end = index + c.len_utf8();
+            if self.digit_value(c).is_some() {
+                (len, prev_separator) = (end, false);
+                continue;
+            }
+
+            let placed = match index {
+                0 => prefixed && separators.after_prefix,
+                _ => !prev_separator || separators.consecutive,
+            };
+            if !placed || !separators.chars.contains(&c) {
+                break;
+            }
+
+            prev_separator = true;
+            if separators.trailing {
+                len = end;
+            }
+        }
+        len
+    }
+
+    /// The error for `src` not matching the affixes, pointing at the affix that was expected
+    ///
+    /// `partial` indicates that the suffix was expected directly after the digits.
+    fn mismatch<T: Num>(&self, src: &str, partial: bool) -> ParseError<'a, T> {
+        let unsigned = split_sign(src).1;
+        let offset = match self.match_prefix(unsigned) {
+            None => src.len() - unsigned.len(),
+            Some(prefix) if partial => {
+                let rest = &unsigned[prefix.len()..];
+                src.len() - rest.len() + self.digits_len(rest, !prefix.is_empty())
+            }
+            Some(_) => src.len(),
+        };
+
+        ParseError::at(
+            ErrorKind::NoPrefixMatch,
+            offset,
+            src[offset..].chars().next(),
+        )
+        .with_fmt(self)
+    }
+
+    fn starts_with(&self, src: &str, spelling: &str) -> bool {
+        match self.case_sensitive {
+            // most formats leave an affix empty, which needs no comparison
+            _ if spelling.is_empty() => true,
+            true => src.starts_with(spelling),
+            false => src
+                .get(..spelling.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(spelling)),
+        }
+    }
+
+    fn ends_with(&self, src: &str, spelling: &str) -> bool {
+        match self.case_sensitive {
+            _ if spelling.is_empty() => true,
+            true => src.ends_with(spelling),
+            false => src
+                .len()
+                .checked_sub(spelling.len())
+                .and_then(|start| src.get(start..))
+                .is_some_and(|tail| tail.eq_ignore_ascii_case(spelling)),
+        }
+    }
+}
+
+/// The longest of `spelling` and its `aliases` that `matches`
+fn longest<'a>(
+    spelling: &'a str,
+    aliases: &'a [&'a str],
+    matches: impl Fn(&str) -> bool,
+) -> Option<&'a str> {
+    let mut best = matches(spelling).then_some(spelling);
+    for &alias in aliases {
+        if matches(alias) && best.is_none_or(|best| alias.len() >= best.len()) {
+            best = Some(alias);
+        }
+    }
+    best
+}
+
+/// A number split by a [`PrefixFmt`] into its sign, affixes and digits
+struct Parts<'s> {
+    src: &'s str,
+    negative: bool,
+    /// The prefix as written in `src`
+    prefix: &'s str,
+    digits: &'s str,
+}
+
+impl<'s> Parts<'s> {
+    /// Splits `src` into its sign and digits, without any affixes
+    fn unprefixed(src: &'s str) -> Self {
+        let (negative, digits) = split_sign(src);
+        Parts {
+            src,
+            negative,
+            prefix: "",
+            digits,
+        }
+    }
+
+    /// Returns true if a non-empty prefix sits between the sign and the digits
+    fn prefixed(&self) -> bool {
+        !self.prefix.is_empty()
+    }
+
+    /// The byte offset of the digits in `src`
+    fn digits_offset(&self) -> usize {
+        self.digits.as_ptr() as usize - self.src.as_ptr() as usize
+    }
+
+    /// The character at byte `offset` of `src`, if any
+    fn char_at(&self, offset: usize) -> Option<char> {
+        self.src[offset..].chars().next()
+    }
+
+    /// The sign and digits, if they are adjacent in `src`
+    fn signed_digits(&self) -> Option<&str> {
+        let sign_len = self.src.len() - split_sign(self.src).1.len();
+        (!self.prefixed()).then(|| &self.src[..sign_len + self.digits.len()])
+    }
+}
+
+/// Defines which digit separators are accepted, and where.
+///
+/// Separators are never accepted before the first digit of an unprefixed number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Separators<'a> {
+    /// Characters accepted as separators, e.g. `_`, `'` or `,`
+    pub chars: &'a [char],
+    /// Allow a separator directly after the prefix, e.g. `0x_FF`
+    pub after_prefix: bool,
+    /// Allow runs of separators, e.g. `1__000`
+    pub consecutive: bool,
+    /// Allow a separator after the last digit, e.g. `1000_`
+    pub trailing: bool,
+}
+
+impl Separators<'_> {
+    /// No separators accepted
+    pub const NONE: Separators<'static> = Separators {
+        chars: &[],
+        after_prefix: false,
+        consecutive: false,
+        trailing: false,
+    };
+
+    /// Checks the placement of the separators in `digits`.
+    ///
+    /// `prefixed` indicates that `digits` followed a non-empty prefix. Errors carry the byte
+    /// offset of the offending separator in `digits`.
+    fn check(&self, digits: &str, prefixed: bool) -> Result<(), (usize, SeparatorError)> {
+        if self.chars.is_empty() {
+            return Ok(());
+        }
+
+        let mut prev_separator = None;
+        for (index, c) in digits.char_indices() {
+            if !self.chars.contains(&c) {
+                prev_separator = None;
+                continue;
+            }
+
+            match (index, prefixed) {
+                (0, false) => return Err((index, SeparatorError::Leading)),
+                (0, true) if !self.after_prefix => {
+                    return Err((index, SeparatorError::AfterPrefix));
+                }
+                _ if prev_separator.is_some() && !self.consecutive => {
+                    return Err((index, SeparatorError::Consecutive));
+                }
+                _ => prev_separator = prev_separator.or(Some(index)),
+            }
+        }
+
+        match prev_separator.filter(|_| !self.trailing) {
+            Some(index) => Err((index, SeparatorError::Trailing)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// '0x' prefix for hexadecimal numbers, '0X' also accepted
+pub const HEX: PrefixFmt = valid(PrefixFmt::new("0x", 16)).case_insensitive();
+/// '0o' prefix for octal numbers, '0O' also accepted
+pub const OCT: PrefixFmt = valid(PrefixFmt::new("0o", 8)).case_insensitive();
+
+/// '0b' prefix for binary numbers, '0B' also accepted
+pub const BIN: PrefixFmt = valid(PrefixFmt::new("0b", 2)).case_insensitive();
 
 /// '' prefix for decimal numbers
-pub const DEC: PrefixFmt = PrefixFmt {
-    prefix: "",
-    radix: 10,
+pub const DEC: PrefixFmt = valid(PrefixFmt::new("", 10));
+
+/// 'h' suffix for hexadecimal numbers, as in `0FFh`
+pub const HEX_SUFFIX: PrefixFmt = valid(PrefixFmt::suffixed("h", 16)).case_insensitive();
+
+/// 'o' suffix for octal numbers, as in `17o`, with 'q' also accepted
+pub const OCT_SUFFIX: PrefixFmt =
+    valid(valid(PrefixFmt::suffixed("o", 8)).with_suffix_aliases(&["q"])).case_insensitive();
+
+/// 'b' suffix for binary numbers, as in `1010b`, with 'y' also accepted
+pub const BIN_SUFFIX: PrefixFmt =
+    valid(valid(PrefixFmt::suffixed("b", 2)).with_suffix_aliases(&["y"])).case_insensitive();
+
+/// 'd' suffix for decimal numbers, as in `99d`, with 't' also accepted
+pub const DEC_SUFFIX: PrefixFmt =
+    valid(valid(PrefixFmt::suffixed("d", 10)).with_suffix_aliases(&["t"])).case_insensitive();
+
+/// Underscores anywhere after the first digit or the prefix, as in Rust literals
+const UNDERSCORES: Separators<'static> = Separators {
+    chars: &['_'],
+    after_prefix: true,
+    consecutive: true,
+    trailing: true,
 };
 
+/// '0x' prefix for hexadecimal numbers, in lowercase only as in Rust literals
+const HEX_LOWER: PrefixFmt = valid(PrefixFmt::new("0x", 16));
+/// '0o' prefix for octal numbers, in lowercase only as in Rust literals
+const OCT_LOWER: PrefixFmt = valid(PrefixFmt::new("0o", 8));
+/// '0b' prefix for binary numbers, in lowercase only as in Rust literals
+const BIN_LOWER: PrefixFmt = valid(PrefixFmt::new("0b", 2));
+
+/// Single underscores between digits or after the prefix, as in Python literals
+const SINGLE_UNDERSCORES: Separators<'static> = Separators {
+    chars: &['_'],
+    after_prefix: true,
+    consecutive: false,
+    trailing: false,
+};
+
+/// Unwraps a built-in format at compile time
+const fn valid<T: Copy>(result: Result<T, FmtError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(_) => panic!("invalid built-in format"),
+    }
+}
+
 /// Trait for parsing prefixed numbers
 pub trait PrefixParse {
     /// Parse a number prefixed with `0x`, `0o`, and `0b`
     ///
+    /// Prefixes are matched regardless of case, so `0X`, `0O` and `0B` are also accepted.
+    ///
+    /// An optional `+` or `-` sign may precede the prefix. A `-` sign is rejected with
+    /// [`ErrorKind::NegativeUnsigned`] for types that cannot represent negative values.
+    ///
+    /// For `f32` and `f64`, hexadecimal digits are read as a C99 hex float, with an optional
+    /// point and a binary exponent after `p`, and rounded correctly to nearest, ties to even.
+    /// Decimal numbers follow Rust's float syntax, including `inf` and `nan`, and C's `nan(...)`.
+    ///
     /// # Example
     /// ```rust
-    /// use prefix_parse::PrefixParse;
+    /// use prefix_parse::{ErrorKind, PrefixParse};
     ///
     /// assert_eq!(u32::parse("0x10"), Ok(16));
     /// assert_eq!(u32::parse("0o10"), Ok(8));
     /// assert_eq!(u32::parse("0b10"), Ok(2));
     /// assert_eq!(u32::parse("10"), Ok(10));
+    /// assert_eq!(u32::parse("0XFF"), Ok(255));
+    /// assert_eq!(u32::parse("0B1010"), Ok(10));
+    ///
+    /// assert_eq!(i32::parse("-0x10"), Ok(-16));
+    /// assert_eq!(i32::parse("+0b101"), Ok(5));
+    /// assert_eq!(i32::parse("-0x80000000"), Ok(i32::MIN));
+    ///
+    /// let error = u32::parse("-0x10").unwrap_err();
+    /// assert_eq!((error.kind(), error.offset()), (ErrorKind::NegativeUnsigned, 0));
+    /// let error = i32::parse("0x-10").unwrap_err();
+    /// assert_eq!((error.kind(), error.offset()), (ErrorKind::MisplacedSign, 2));
+    /// let error = u8::parse("0x1FF").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::PosOverflow);
+    ///
+    /// assert_eq!(f64::parse("0x1.8p3").ok(), Some(12.0));
+    /// assert_eq!(f64::parse("-0x1p-1074").ok(), Some(-f64::from_bits(1)));
+    /// assert_eq!(f32::parse("0x1.000001p0").ok(), Some(1.0));
+    /// assert_eq!(f32::parse("0x1p128").ok(), Some(f32::INFINITY));
+    /// assert!(f64::parse("-nan(0x1)").is_ok_and(f64::is_nan));
+    /// let error = f64::parse("0x1p").unwrap_err();
+    /// assert_eq!((error.kind(), error.offset()), (ErrorKind::InvalidDigit, 3));
     /// ```
-    fn parse(src: &str) -> Result<Self, ParseError<Self>>
+    fn parse(src: &str) -> Result<Self, ParseError<'static, Self>>
     where
         Self: Sized + Num,
     {
-        // SAFETY: if src is a valid UTF-8 string, and we strip no multibyte characters from the start,
-        // then the remaining string will be valid UTF-8
-        match src.as_bytes() {
-            [b'0', b'x', rest @ ..] => {
-                Self::from_str_radix(unsafe { str::from_utf8_unchecked(rest) }, 16)
-                    .map_err(ParseError::RadixParseFailed)
-            }
-            [b'0', b'o', rest @ ..] => {
-                Self::from_str_radix(unsafe { str::from_utf8_unchecked(rest) }, 8)
-                    .map_err(ParseError::RadixParseFailed)
-            }
-            [b'0', b'b', rest @ ..] => {
-                Self::from_str_radix(unsafe { str::from_utf8_unchecked(rest) }, 2)
-                    .map_err(ParseError::RadixParseFailed)
-            }
-            _ => Self::from_str_radix(src, 10).map_err(ParseError::RadixParseFailed),
-        }
+        let (fmt, parts) = split_builtin(src);
+        from_signed_digits(&parts, fmt)
+    }
+
+    /// Parse a number like [`PrefixParse::parse`], also returning the notation it was written in
+    ///
+    /// # Example
+    /// ```rust
+    /// use prefix_parse::{LetterCase, PrefixParse, HEX};
+    ///
+    /// let detected = u32::parse_detect("0x00FF").unwrap();
+    /// assert_eq!(detected.value, 255);
+    /// assert_eq!(detected.fmt, &HEX);
+    /// assert_eq!(detected.prefix_case, LetterCase::Lower);
+    /// assert_eq!(detected.digits, 4);
+    /// assert_eq!(detected.digit_case, LetterCase::Upper);
+    /// ```
+    fn parse_detect(src: &str) -> Result<Detected<'static, Self>, ParseError<'static, Self>>
+    where
+        Self: Sized + Num,
+    {
+        let (fmt, parts) = split_builtin(src);
+        let value = from_signed_digits(&parts, fmt)?;
+
+        Ok(Detected {
+            value,
+            fmt,
+            prefix: fmt.match_prefix(parts.prefix).unwrap_or(fmt.prefix),
+            prefix_case: LetterCase::of(parts.prefix),
+            digits: parts
+                .digits
+                .chars()
+                .filter(|c| !fmt.separators.chars.contains(c))
+                .count(),
+            digit_case: LetterCase::of(parts.digits),
+        })
     }
 
     /// Parse a number with a custom prefix
     ///
+    /// Signs are handled the same way as in [`PrefixParse::parse`].
+    ///
     /// # Example
     /// ```
-    /// use prefix_parse::{PrefixParse, ParseError, PrefixFmt, HEX};
+    /// use prefix_parse::{
+    ///     Alphabet, ErrorKind, PrefixFmt, PrefixParse, SeparatorError, Separators, HEX,
+    /// };
     ///
     /// assert_eq!(u32::parse_with(&HEX, "0x10"), Ok(16));
+    /// assert_eq!(i32::parse_with(&HEX, "-0x10"), Ok(-16));
     ///
-    /// let custom_fmt = PrefixFmt {
-    ///     prefix: "0z",
-    ///     radix: 36,
-    /// };
+    /// let custom_fmt = PrefixFmt::new("0z", 36)?;
     /// assert_eq!(u32::parse_with(&custom_fmt, "0z1jz"), Ok(2015));
+    ///
+    /// let vb_hex = PrefixFmt::new("&H", 16)?
+    ///     .with_aliases(&["#", "$"])?
+    ///     .case_insensitive();
+    /// assert_eq!(u32::parse_with(&vb_hex, "&hFF"), Ok(255));
+    /// assert_eq!(u32::parse_with(&vb_hex, "$FF"), Ok(255));
+    ///
+    /// let crockford = PrefixFmt::from_alphabet("", &Alphabet::CROCKFORD);
+    /// assert_eq!(u32::parse_with(&crockford, "1o"), Ok(32));
+    ///
+    /// let cpp_bin = PrefixFmt::new("0b", 2)?.with_separators(Separators {
+    ///     chars: &['\''],
+    ///     after_prefix: false,
+    ///     consecutive: false,
+    ///     trailing: false,
+    /// });
+    /// assert_eq!(u32::parse_with(&cpp_bin, "0b1010'0101"), Ok(0b1010_0101));
+    ///
+    /// let error = u32::parse_with(&cpp_bin, "0b1010''0101").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::Separator(SeparatorError::Consecutive));
+    /// assert_eq!((error.offset(), error.found()), (7, Some('\'')));
+    ///
+    /// let error = u32::parse_with(&cpp_bin, "0b'1010").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::Separator(SeparatorError::AfterPrefix));
+    /// assert_eq!(error.offset(), 2);
+    ///
+    /// let error = i32::parse_with(&cpp_bin, "0b0'-1").unwrap_err();
+    /// assert_eq!((error.kind(), error.offset()), (ErrorKind::InvalidDigit, 4));
+    ///
+    /// let error = u32::parse_with(&cpp_bin, "0x1010").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::NoPrefixMatch);
+    /// assert_eq!(error.fmt(), Some(&cpp_bin));
+    /// # Ok::<(), prefix_parse::FmtError>(())
+    /// ```
+    fn parse_with<'f>(fmt: &PrefixFmt<'f>, src: &str) -> Result<Self, ParseError<'f, Self>>
+    where
+        Self: Sized + Num,
+    {
+        fmt.split(src)
+            .ok_or_else(|| fmt.mismatch(src, false))?
+            .pipe(|parts| from_signed_digits(&parts, fmt))
+    }
+
+    /// Parse a number in any format of a [`PrefixSet`] or [`PrefixTrie`], detecting the format as
+    /// [`PrefixParse::parse`] does
+    ///
+    /// See [`PrefixSet`] for how the format is chosen.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{ErrorKind, PrefixParse, PrefixSet, HEX, HEX_SUFFIX};
+    ///
+    /// let set = PrefixSet::new(&[&HEX, &HEX_SUFFIX]).unwrap().without_fallback();
+    /// assert_eq!(u32::parse_in(&set, "0x1F"), Ok(31));
+    /// assert_eq!(u32::parse_in(&set, "1Fh"), Ok(31));
+    ///
+    /// let error = u32::parse_in(&set, "-31").unwrap_err();
+    /// assert_eq!((error.kind(), error.offset()), (ErrorKind::NoPrefixMatch, 1));
+    /// ```
+    fn parse_in<'f>(
+        matcher: &impl PrefixMatcher<'f>,
+        src: &str,
+    ) -> Result<Self, ParseError<'f, Self>>
+    where
+        Self: Sized + Num,
+    {
+        matcher
+            .detect(src)
+            .and_then(|fmt| fmt.split(src).map(|parts| (fmt, parts)))
+            .ok_or_else(|| set::mismatch(matcher, src))
+            .and_then(|(fmt, parts)| from_signed_digits(&parts, fmt))
+    }
+
+    /// Parse the longest number at the front of `src`, returning it with the unconsumed remainder
+    ///
+    /// Prefixes and signs are detected as in [`PrefixParse::parse`]. If a prefix is not followed
+    /// by any digits, the number is read as decimal instead, so `0xyz` parses as `0` leaving
+    /// `xyz`. Only integer digits are consumed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use prefix_parse::PrefixParse;
+    ///
+    /// assert_eq!(u32::parse_partial("0x1F,rest"), Ok((31, ",rest")));
+    /// assert_eq!(i32::parse_partial("-0b101)"), Ok((-5, ")")));
+    /// assert_eq!(u32::parse_partial("42"), Ok((42, "")));
+    /// assert_eq!(u32::parse_partial("0xyz"), Ok((0, "xyz")));
+    /// assert!(u32::parse_partial("xyz").is_err());
     /// ```
-    fn parse_with(fmt: &PrefixFmt, src: &str) -> Result<Self, ParseError<Self>>
+    fn parse_partial(src: &str) -> Result<(Self, &str), ParseError<'static, Self>>
     where
         Self: Sized + Num,
     {
-        src.strip_prefix(fmt.prefix)
-            .ok_or(ParseError::NoPrefixMatch)?
-            .pipe(|rest| Self::from_str_radix(rest, fmt.radix))
-            .map_err(ParseError::RadixParseFailed)
+        let (fmt, (parts, rest)) = [&HEX, &OCT, &BIN]
+            .into_iter()
+            .filter_map(|fmt| fmt.split_partial(src).map(|split| (fmt, split)))
+            .find(|(_, (parts, _))| !parts.digits.is_empty())
+            .or_else(|| DEC.split_partial(src).map(|split| (&DEC, split)))
+            .ok_or_else(|| DEC.mismatch(src, true))?;
+
+        from_signed_digits(&parts, fmt).map(|value| (value, rest))
+    }
+
+    /// Parse the longest number with a custom prefix at the front of `src`, returning it with
+    /// the unconsumed remainder
+    ///
+    /// # Example
+    /// ```rust
+    /// use prefix_parse::{PrefixParse, HEX, HEX_SUFFIX};
+    ///
+    /// assert_eq!(u32::parse_partial_with(&HEX, "0xFF + 1"), Ok((255, " + 1")));
+    /// assert_eq!(u32::parse_partial_with(&HEX_SUFFIX, "0FFh, 1"), Ok((255, ", 1")));
+    /// ```
+    fn parse_partial_with<'f, 's>(
+        fmt: &PrefixFmt<'f>,
+        src: &'s str,
+    ) -> Result<(Self, &'s str), ParseError<'f, Self>>
+    where
+        Self: Sized + Num,
+    {
+        let (parts, rest) = fmt
+            .split_partial(src)
+            .ok_or_else(|| fmt.mismatch(src, true))?;
+        from_signed_digits(&parts, fmt).map(|value| (value, rest))
+    }
+
+    /// Parse a number marked with an assembler-style suffix: `h`, `o`/`q`, `b`/`y` or `d`/`t`
+    ///
+    /// Unsuffixed numbers are decimal. Suffixes are matched regardless of case. Following the
+    /// MASM/NASM convention, hexadecimal numbers must start with a decimal digit, so `1010b` is
+    /// binary, `1010bh` is hexadecimal, and `FFh` is rejected in favour of `0FFh`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use prefix_parse::{ErrorKind, PrefixParse};
+    ///
+    /// assert_eq!(u32::parse_suffixed("0FFh"), Ok(255));
+    /// assert_eq!(u32::parse_suffixed("17o"), Ok(15));
+    /// assert_eq!(u32::parse_suffixed("17Q"), Ok(15));
+    /// assert_eq!(u32::parse_suffixed("1010b"), Ok(10));
+    /// assert_eq!(u32::parse_suffixed("1010bh"), Ok(0x1010b));
+    /// assert_eq!(u32::parse_suffixed("99"), Ok(99));
+    /// assert_eq!(i32::parse_suffixed("-10h"), Ok(-16));
+    /// assert_eq!(
+    ///     u32::parse_suffixed("FFh").map_err(|error| error.kind()),
+    ///     Err(ErrorKind::NoPrefixMatch)
+    /// );
+    /// ```
+    fn parse_suffixed(src: &str) -> Result<Self, ParseError<'static, Self>>
+    where
+        Self: Sized + Num,
+    {
+        let (fmt, parts) = [&HEX_SUFFIX, &OCT_SUFFIX, &BIN_SUFFIX, &DEC_SUFFIX, &DEC]
+            .into_iter()
+            .find_map(|fmt| fmt.split(src).map(|parts| (fmt, parts)))
+            .ok_or_else(|| DEC.mismatch(src, false))?;
+
+        match fmt.radix == 16 && !parts.digits.starts_with(|c: char| c.is_ascii_digit()) {
+            true => {
+                let offset = parts.digits_offset();
+                Err(
+                    ParseError::at(ErrorKind::NoPrefixMatch, offset, parts.char_at(offset))
+                        .with_fmt(fmt),
+                )
+            }
+            false => from_signed_digits(&parts, fmt),
+        }
+    }
+
+    /// Parse a number with a custom prefix, accumulating its digits directly
+    ///
+    /// Unlike [`PrefixParse::parse_with`], this does not rely on `from_str_radix`, so it supports
+    /// formats with an [`Alphabet`] of more than 36 digits.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{Alphabet, ErrorKind, PrefixFmt, PrefixParse};
+    ///
+    /// let base62 = PrefixFmt::from_alphabet("", &Alphabet::BASE62);
+    /// assert_eq!(u32::parse_digits_with(&base62, "zz"), Ok(3843));
+    /// assert_eq!(i32::parse_digits_with(&base62, "-zz"), Ok(-3843));
+    /// let error = u8::parse_digits_with(&base62, "zz").unwrap_err();
+    /// assert_eq!((error.kind(), error.offset()), (ErrorKind::PosOverflow, 1));
+    ///
+    /// let crockford = PrefixFmt::from_alphabet("", &Alphabet::CROCKFORD);
+    /// assert_eq!(u32::parse_digits_with(&crockford, "1O"), Ok(32));
+    /// let error = u32::parse_digits_with(&crockford, "1U").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::InvalidDigit);
+    /// assert_eq!((error.offset(), error.found()), (1, Some('U')));
+    ///
+    /// // one digit per byte value, drawn from U+0100..=U+01FF
+    /// let digits: String = ('\u{100}'..='\u{1FF}').collect();
+    /// let bytes = Alphabet::new(&digits)?;
+    /// let base256 = PrefixFmt::from_alphabet("0r", &bytes);
+    /// assert_eq!(base256.radix(), 256);
+    /// assert_eq!(u16::parse_digits_with(&base256, "0rāĀ"), Ok(256));
+    /// assert_eq!(u8::parse_digits_with(&base256, "0rǿ"), Ok(255));
+    /// assert_eq!(
+    ///     u8::parse_digits_with(&base256, "0rāĀ").map_err(|error| error.kind()),
+    ///     Err(ErrorKind::PosOverflow)
+    /// );
+    /// # Ok::<(), prefix_parse::FmtError>(())
+    /// ```
+    fn parse_digits_with<'f>(fmt: &PrefixFmt<'f>, src: &str) -> Result<Self, ParseError<'f, Self>>
+    where
+        Self: Sized + Num + CheckedAdd + CheckedSub + CheckedMul + FromPrimitive,
+    {
+        fmt.split(src)
+            .ok_or_else(|| fmt.mismatch(src, false))?
+            .pipe(|parts| accumulate_digits(&parts, fmt))
+    }
+
+    /// Parse a number like [`PrefixParse::parse`], with [`ParseOptions`]
+    ///
+    /// Values that do not fit the type are handled by the options' [`Overflow`] policy.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{ParseOptions, PrefixParse};
+    ///
+    /// let opts = ParseOptions::new().bit_pattern();
+    /// assert_eq!(i8::parse_opts(&opts, "0xFF"), Ok(-1));
+    /// assert_eq!(i16::parse_opts(&opts, "0x8000"), Ok(i16::MIN));
+    /// assert_eq!(i8::parse_opts(&opts, "-0x01"), Ok(-1));
+    /// assert_eq!(i8::parse_opts(&opts, "-1"), Ok(-1));
+    /// assert!(i8::parse_opts(&opts, "0x1FF").is_err());
+    /// ```
+    fn parse_opts(opts: &ParseOptions, src: &str) -> Result<Self, ParseError<'static, Self>>
+    where
+        Self: Sized + PrimInt,
+    {
+        Self::parse_overflowing(opts, src).map(|(value, _)| value)
+    }
+
+    /// Parse a number with a custom prefix, with [`ParseOptions`]
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{ParseOptions, PrefixParse, HEX_SUFFIX};
+    ///
+    /// let opts = ParseOptions::new().bit_pattern();
+    /// assert_eq!(i32::parse_with_opts(&HEX_SUFFIX, &opts, "0FFFFFFFFh"), Ok(-1));
+    /// ```
+    fn parse_with_opts<'f>(
+        fmt: &PrefixFmt<'f>,
+        opts: &ParseOptions,
+        src: &str,
+    ) -> Result<Self, ParseError<'f, Self>>
+    where
+        Self: Sized + PrimInt,
+    {
+        Self::parse_with_overflowing(fmt, opts, src).map(|(value, _)| value)
+    }
+
+    /// Parse a number like [`PrefixParse::parse_opts`], also returning whether the options'
+    /// [`Overflow`] policy was applied
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{Overflow, ParseOptions, PrefixParse};
+    ///
+    /// let wrap = ParseOptions::new().overflow(Overflow::Wrap);
+    /// assert_eq!(u8::parse_overflowing(&wrap, "0x1FF"), Ok((0xFF, true)));
+    /// assert_eq!(u8::parse_overflowing(&wrap, "-1"), Ok((0xFF, true)));
+    /// assert_eq!(u8::parse_overflowing(&wrap, "0x10"), Ok((0x10, false)));
+    ///
+    /// let saturate = ParseOptions::new().overflow(Overflow::Saturate);
+    /// assert_eq!(i8::parse_overflowing(&saturate, "-1000"), Ok((i8::MIN, true)));
+    /// assert_eq!(u8::parse_overflowing(&saturate, "-1"), Ok((0, true)));
+    ///
+    /// let truncate = ParseOptions::new().overflow(Overflow::Truncate);
+    /// assert_eq!(i8::parse_overflowing(&truncate, "-0x1FF"), Ok((-0x7F, true)));
+    /// ```
+    fn parse_overflowing(
+        opts: &ParseOptions,
+        src: &str,
+    ) -> Result<(Self, bool), ParseError<'static, Self>>
+    where
+        Self: Sized + PrimInt,
+    {
+        let (fmt, parts) = split_builtin(src);
+        options::parse_parts(&parts, fmt, opts)
+    }
+
+    /// Parse a number like [`PrefixParse::parse_with_opts`], also returning whether the options'
+    /// [`Overflow`] policy was applied
+    fn parse_with_overflowing<'f>(
+        fmt: &PrefixFmt<'f>,
+        opts: &ParseOptions,
+        src: &str,
+    ) -> Result<(Self, bool), ParseError<'f, Self>>
+    where
+        Self: Sized + PrimInt,
+    {
+        fmt.split(src)
+            .ok_or_else(|| fmt.mismatch(src, false))?
+            .pipe(|parts| options::parse_parts(&parts, fmt, opts))
+    }
+
+    /// Parse a number like [`PrefixParse::parse`] into a field of `N` bits, such as a `u12` or
+    /// an `i20`
+    ///
+    /// Values that do not fit the field fail with [`ErrorKind::PosWidthOverflow`] or
+    /// [`ErrorKind::NegWidthOverflow`], stating the bound in the notation of the input. For a
+    /// width chosen at runtime, use [`ParseOptions::width`].
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{ErrorKind, PrefixParse};
+    ///
+    /// assert_eq!(u16::parse_bits::<12>("0xFFF"), Ok(0xFFF));
+    /// assert_eq!(i32::parse_bits::<20>("-0x80000"), Ok(-0x80000));
+    ///
+    /// let error = u16::parse_bits::<12>("0x1000").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::PosWidthOverflow { bits: 12, signed: false });
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Number Too Large To Fit In 12 Bits At Byte 2, Assuming Radix 16, Max 0xfff"
+    /// );
+    ///
+    /// let error = i8::parse_bits::<4>("-0b1001").unwrap_err();
+    /// assert!(error.to_string().ends_with("Min -0b1000"));
+    /// ```
+    ///
+    /// A zero width fails to compile:
+    /// ```compile_fail
+    /// # use prefix_parse::PrefixParse;
+    /// let value = u8::parse_bits::<0>("0");
+    /// ```
+    fn parse_bits<const N: u32>(src: &str) -> Result<Self, ParseError<'static, Self>>
+    where
+        Self: Sized + PrimInt,
+    {
+        Self::parse_opts(&const { ParseOptions::new().width(N) }, src)
+    }
+
+    /// Parse a number with a custom prefix into a field of `N` bits
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{PrefixParse, HEX_SUFFIX};
+    ///
+    /// assert_eq!(u16::parse_bits_with::<12>(&HEX_SUFFIX, "0FFFh"), Ok(0xFFF));
+    ///
+    /// let error = u16::parse_bits_with::<12>(&HEX_SUFFIX, "1000h").unwrap_err();
+    /// assert!(error.to_string().ends_with("Max 0fffh"));
+    /// ```
+    fn parse_bits_with<'f, const N: u32>(
+        fmt: &PrefixFmt<'f>,
+        src: &str,
+    ) -> Result<Self, ParseError<'f, Self>>
+    where
+        Self: Sized + PrimInt,
+    {
+        Self::parse_with_opts(fmt, &const { ParseOptions::new().width(N) }, src)
     }
 }
 
 /// Implementation for all number types that implement the `Num` interface.
 impl<T: Num> PrefixParse for T {}
 
-/// Error type for `PrefixParse`
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-pub enum ParseError<T: Num> {
-    #[error("No Prefix Match")]
-    NoPrefixMatch,
-    #[error(transparent)]
-    RadixParseFailed(T::FromStrRadixErr),
+/// Splits `src` with the first built-in format whose prefix matches, falling back to decimal.
+fn split_builtin(src: &str) -> (&'static PrefixFmt<'static>, Parts<'_>) {
+    PrefixSet::BUILTIN
+        .split(src)
+        .unwrap_or_else(|| (&DEC, Parts::unprefixed(src)))
+}
+
+/// Splits a leading `+` or `-` sign from `src`, returning whether the value is negative.
+fn split_sign(src: &str) -> (bool, &str) {
+    match src.as_bytes() {
+        [b'-', ..] => (true, &src[1..]),
+        [b'+', ..] => (false, &src[1..]),
+        _ => (false, src),
+    }
+}
+
+/// The [`TypeId`] of `T` with its lifetimes erased, so that any type can be compared with the
+/// primitives, as the `typeid` crate does.
+pub(crate) fn type_id<T: ?Sized>() -> TypeId {
+    trait NonStaticAny {
+        fn type_id(&self) -> TypeId
+        where
+            Self: 'static;
+    }
+
+    impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+        fn type_id(&self) -> TypeId
+        where
+            Self: 'static,
+        {
+            TypeId::of::<T>()
+        }
+    }
+
+    let phantom = PhantomData::<T>;
+    let erased: &dyn NonStaticAny = &phantom;
+    // SAFETY: only the lifetime of the trait object is extended, and `type_id` reads nothing
+    // through it, while `TypeId` never depends on lifetimes
+    let erased: &(dyn NonStaticAny + 'static) = unsafe { core::mem::transmute(erased) };
+    erased.type_id()
+}
+
+/// Returns true if `T` rejects negative values, as unsigned integers do.
+///
+/// Primitives are told apart by their type, and only other types are asked to parse `-1`.
+fn is_unsigned<T: Num>() -> bool {
+    const UNSIGNED: [TypeId; 6] = [
+        TypeId::of::<u8>(),
+        TypeId::of::<u16>(),
+        TypeId::of::<u32>(),
+        TypeId::of::<u64>(),
+        TypeId::of::<u128>(),
+        TypeId::of::<usize>(),
+    ];
+    // floats take a sign as well
+    const SIGNED: [TypeId; 8] = [
+        TypeId::of::<i8>(),
+        TypeId::of::<i16>(),
+        TypeId::of::<i32>(),
+        TypeId::of::<i64>(),
+        TypeId::of::<i128>(),
+        TypeId::of::<isize>(),
+        TypeId::of::<f32>(),
+        TypeId::of::<f64>(),
+    ];
+
+    let id = type_id::<T>();
+    match () {
+        _ if UNSIGNED.contains(&id) => true,
+        _ if SIGNED.contains(&id) => false,
+        _ => T::from_str_radix("-1", 10).is_err(),
+    }
+}
+
+/// Checks the sign and separators of the digits.
+///
+/// `negatives` indicates that negative values are accepted.
+fn check_digits<'f, T: Num>(
+    parts: &Parts,
+    fmt: &PrefixFmt<'f>,
+    negatives: bool,
+) -> Result<(), ParseError<'f, T>> {
+    let start = parts.digits_offset();
+    if parts.digits.starts_with(['+', '-']) {
+        let error = ParseError::at(ErrorKind::MisplacedSign, start, parts.char_at(start));
+        return Err(error.with_fmt(fmt));
+    }
+
+    if parts.negative && !negatives {
+        let error = ParseError::at(ErrorKind::NegativeUnsigned, 0, Some('-'));
+        return Err(error.with_fmt(fmt));
+    }
+
+    fmt.separators
+        .check(parts.digits, parts.prefixed())
+        .map_err(|(index, error)| {
+            let offset = start + index;
+            ParseError::at(ErrorKind::Separator(error), offset, parts.char_at(offset)).with_fmt(fmt)
+        })
+}
+
+/// Parses the digits of `parts` with `fmt`, applying their sign.
+///
+/// The sign is handed to `from_str_radix` along with the digits, so that values like `i32::MIN`,
+/// whose magnitude does not fit the type, still parse.
+fn from_signed_digits<'f, T: Num>(
+    parts: &Parts,
+    fmt: &PrefixFmt<'f>,
+) -> Result<T, ParseError<'f, T>> {
+    check_digits(parts, fmt, !is_unsigned::<T>())?;
+    // floats are read exactly in hexadecimal, rather than digit by digit by `from_str_radix`
+    if fmt.alphabet.is_none()
+        && (fmt.radix == 16 || float::is_nan_payload(parts.digits))
+        && let Some(result) = float::parse(parts, fmt)
+    {
+        return result;
+    }
+    if fmt.alphabet.is_some() && fmt.radix > 36 {
+        let error = ParseError::at(ErrorKind::UnsupportedRadix(fmt.radix), 0, None);
+        return Err(error.with_fmt(fmt));
+    }
+
+    let separated = !fmt.separators.chars.is_empty() && parts.digits.contains(fmt.separators.chars);
+    let direct = match (fmt.alphabet, separated) {
+        (None, false) if !parts.negative => Some(parts.digits),
+        // no prefix between the sign and the digits, so the sign is still attached to them
+        (None, false) => parts.signed_digits(),
+        _ => None,
+    };
+
+    let mut buf = DigitBuf::new();
+    let digits = match direct {
+        Some(digits) => digits,
+        None if normalize(parts, fmt, &mut buf).is_ok() => buf.as_str(),
+        None => return Err(diagnose(parts, fmt)),
+    };
+
+    T::from_str_radix(digits, fmt.radix).map_err(|source| diagnose(parts, fmt).with_source(source))
+}
+
+/// Rewrites the digits of `parts` for `from_str_radix`, with their sign, without separators or
+/// leading zeros, and in standard digits.
+fn normalize(parts: &Parts, fmt: &PrefixFmt, buf: &mut DigitBuf) -> core::fmt::Result {
+    if parts.negative {
+        buf.write_char('-')?;
+    }
+
+    let mut leading_zeros = None;
+    for c in parts.digits.chars() {
+        if fmt.separators.chars.contains(&c) {
+            continue;
+        }
+        // a sign after leading zeros would be read by `from_str_radix` once they are removed
+        if matches!(c, '+' | '-') {
+            return Err(core::fmt::Error);
+        }
+
+        // spell alphabet digits as standard digits, which `from_str_radix` understands
+        let c = match fmt.alphabet {
+            Some(alphabet) => alphabet
+                .digit_value(c)
+                .and_then(|value| char::from_digit(value, fmt.radix))
+                .unwrap_or(char::REPLACEMENT_CHARACTER),
+            None => c,
+        };
+
+        match (leading_zeros, c) {
+            (None | Some(true), '0') => leading_zeros = Some(true),
+            _ => {
+                leading_zeros = Some(false);
+                buf.write_char(c)?;
+            }
+        }
+    }
+
+    match leading_zeros {
+        Some(true) => buf.write_char('0'),
+        _ => Ok(()),
+    }
+}
+
+/// Works out why `from_str_radix` rejected the digits of `parts`, from the digit rules of `fmt`.
+///
+/// Digits that are all valid can only have been rejected for being empty or out of range.
+fn diagnose<'f, T: Num>(parts: &Parts, fmt: &PrefixFmt<'f>) -> ParseError<'f, T> {
+    let start = parts.digits_offset();
+    let invalid = parts
+        .digits
+        .char_indices()
+        .find(|&(_, c)| fmt.digit_value(c).is_none() && !fmt.separators.chars.contains(&c));
+
+    let error = match invalid {
+        Some((index, c)) => ParseError::at(ErrorKind::InvalidDigit, start + index, Some(c)),
+        None if !parts.digits.chars().any(|c| fmt.digit_value(c).is_some()) => {
+            ParseError::at(ErrorKind::Empty, start, None)
+        }
+        None if parts.negative => ParseError::at(ErrorKind::NegOverflow, start, None),
+        None => ParseError::at(ErrorKind::PosOverflow, start, None),
+    };
+    error.with_fmt(fmt)
+}
+
+/// Parses the digits of `parts` with `fmt` by accumulating each digit in turn.
+///
+/// Negative values are accumulated downwards, so that values like `i32::MIN`, whose magnitude
+/// does not fit the type, still parse.
+fn accumulate_digits<'f, T>(parts: &Parts, fmt: &PrefixFmt<'f>) -> Result<T, ParseError<'f, T>>
+where
+    T: Num + CheckedAdd + CheckedSub + CheckedMul + FromPrimitive,
+{
+    check_digits(parts, fmt, !is_unsigned::<T>())?;
+    let start = parts.digits_offset();
+    if parts
+        .digits
+        .chars()
+        .all(|c| fmt.separators.chars.contains(&c))
+    {
+        return Err(ParseError::at(ErrorKind::Empty, start, None).with_fmt(fmt));
+    }
+
+    // the radix need not fit `T`, e.g. base 256 for `u8`, in which case only a zero can be shifted
+    let radix = T::from_u32(fmt.radix);
+    parts
+        .digits
+        .char_indices()
+        .filter(|(_, c)| !fmt.separators.chars.contains(c))
+        .try_fold(T::zero(), |acc, (index, c)| {
+            let error = |kind| ParseError::at(kind, start + index, Some(c)).with_fmt(fmt);
+            let digit = fmt
+                .digit_value(c)
+                .and_then(T::from_u32)
+                .ok_or_else(|| error(ErrorKind::InvalidDigit))?;
+
+            let shifted = match &radix {
+                Some(radix) => acc.checked_mul(radix),
+                None => acc.is_zero().then_some(acc),
+            };
+
+            match parts.negative {
+                true => shifted
+                    .and_then(|acc| acc.checked_sub(&digit))
+                    .ok_or_else(|| error(ErrorKind::NegOverflow)),
+                false => shifted
+                    .and_then(|acc| acc.checked_add(&digit))
+                    .ok_or_else(|| error(ErrorKind::PosOverflow)),
+            }
+        })
 }
@@ -0,0 +1,297 @@
+use num_traits::PrimInt;
+
+use crate::{ErrorKind, ParseError, Parts, PrefixFmt, check_digits};
+
+/// Options changing how [`PrefixParse::parse_opts`](crate::PrefixParse::parse_opts) reads digits.
+///
+/// # Example
+/// ```
+/// use prefix_parse::{ParseOptions, PrefixParse};
+///
+/// const REGISTER: ParseOptions = ParseOptions::new().bit_pattern();
+///
+/// assert_eq!(i8::parse_opts(&REGISTER, "0xFF"), Ok(-1));
+/// assert_eq!(i8::parse_opts(&REGISTER, "0x7F"), Ok(127));
+/// assert!(i8::parse_opts(&REGISTER, "255").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParseOptions {
+    bit_pattern: bool,
+    overflow: Overflow,
+    width: Option<u32>,
+}
+
+impl ParseOptions {
+    /// The default options, parsing digits as a value like [`PrefixParse::parse`]
+    ///
+    /// [`PrefixParse::parse`]: crate::PrefixParse::parse
+    pub const fn new() -> Self {
+        ParseOptions {
+            bit_pattern: false,
+            overflow: Overflow::Error,
+            width: None,
+        }
+    }
+
+    /// Read non-decimal digits as the raw two's-complement bits of the type, so `0xFF` is `-1`
+    /// for an `i8`
+    ///
+    /// Digits are rejected only if they set bits beyond the width of the type. Decimal digits
+    /// are still read as a value, and a sign negates the value the bits represent.
+    pub const fn bit_pattern(self) -> Self {
+        ParseOptions {
+            bit_pattern: true,
+            ..self
+        }
+    }
+
+    /// Handle values that do not fit the type with `overflow`
+    pub const fn overflow(self, overflow: Overflow) -> Self {
+        ParseOptions { overflow, ..self }
+    }
+
+    /// Parse into a field of `bits` bits, such as a 12-bit register field
+    ///
+    /// The value must fit the field as well as the type: an unsigned field holds `0` to
+    /// `2^bits - 1`, and a signed field holds `-2^(bits - 1)` to `2^(bits - 1) - 1`. Overflow
+    /// policies and bit patterns apply at the width of the field. Widths beyond the type are
+    /// limited to the type.
+    ///
+    /// # Panics
+    /// If `bits` is zero.
+    pub const fn width(self, bits: u32) -> Self {
+        assert!(bits > 0, "field width must be at least 1 bit");
+        ParseOptions {
+            width: Some(bits),
+            ..self
+        }
+    }
+
+    /// If true, non-decimal digits are read as two's-complement bits
+    pub const fn is_bit_pattern(&self) -> bool {
+        self.bit_pattern
+    }
+
+    /// The policy for values that do not fit the type
+    pub const fn overflow_policy(&self) -> Overflow {
+        self.overflow
+    }
+
+    /// The width of the field in bits, if narrower than the type
+    pub const fn field_width(&self) -> Option<u32> {
+        self.width
+    }
+}
+
+/// What to do with a value that does not fit the type.
+///
+/// Under a bit-pattern [`ParseOptions`], digits setting bits beyond the width of the type wrap
+/// or truncate to the low bits, or saturate to all bits set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Fail with [`ErrorKind::PosOverflow`] or [`ErrorKind::NegOverflow`]
+    #[default]
+    Error,
+    /// Wrap around modulo the width of the type, as `wrapping_*` arithmetic and C's `strtoul`
+    /// do, so `-1` is the largest value of an unsigned type
+    Wrap,
+    /// Clamp to the smallest or largest value of the type, so `-1` is zero for an unsigned type
+    Saturate,
+    /// Keep the low bits of the magnitude that the type can hold, keeping the sign
+    ///
+    /// Negative values are still rejected for unsigned types.
+    Truncate,
+}
+
+/// The magnitude of a number, as its low 128 bits
+struct Magnitude {
+    low: u128,
+    /// Bits were lost beyond the low 128
+    truncated: bool,
+}
+
+/// Parses the digits of `parts` with `fmt` and `opts` into a primitive integer, returning
+/// whether the overflow policy was applied.
+pub(crate) fn parse_parts<'f, T: PrimInt>(
+    parts: &Parts,
+    fmt: &PrefixFmt<'f>,
+    opts: &ParseOptions,
+) -> Result<(T, bool), ParseError<'f, T>> {
+    let size = T::zero().count_zeros();
+    let bits = opts.width.map_or(size, |width| width.min(size));
+    let signed = T::min_value() < T::zero();
+    let negatives = signed || matches!(opts.overflow, Overflow::Wrap | Overflow::Saturate);
+    check_digits(parts, fmt, negatives)?;
+    let magnitude = accumulate_wide(parts, fmt)?;
+
+    let mask = mask(bits);
+    // the largest positive value and the smallest negative value, as patterns
+    let (max, min) = match signed {
+        true => (mask >> 1, (mask >> 1) + 1),
+        false => (mask, 0),
+    };
+    let negate = |pattern: u128| pattern.wrapping_neg() & mask;
+    let start = parts.digits_offset();
+    // the error for a value above the largest value, which is all bits set for a bit pattern,
+    // or below the smallest value when `negative`
+    let overflow = |negative, pattern: bool| {
+        let kind = match (negative, bits < size) {
+            (true, false) => ErrorKind::NegOverflow,
+            (false, false) => ErrorKind::PosOverflow,
+            (true, true) => ErrorKind::NegWidthOverflow { bits },
+            (false, true) => ErrorKind::PosWidthOverflow {
+                bits,
+                signed: signed && !pattern,
+            },
+        };
+        Err(ParseError::at(kind, start, None).with_fmt(fmt))
+    };
+
+    let (pattern, overflowed) = match opts.bit_pattern && signed && fmt.radix != 10 {
+        // the digits are the bits; the sign negates the value they represent
+        true => {
+            let wide = magnitude.truncated || magnitude.low & !mask != 0;
+            let bits = match (wide, opts.overflow) {
+                (false, _) => magnitude.low,
+                (true, Overflow::Error) => return overflow(false, true),
+                (true, Overflow::Wrap | Overflow::Truncate) => magnitude.low & mask,
+                (true, Overflow::Saturate) => mask,
+            };
+            match (parts.negative, bits == min, opts.overflow) {
+                (false, ..) => (bits, wide),
+                (true, false, _) => (negate(bits), wide),
+                (true, true, Overflow::Error) => return overflow(false, false),
+                (true, true, Overflow::Saturate) => (max, true),
+                (true, true, Overflow::Wrap | Overflow::Truncate) => (min, true),
+            }
+        }
+        false => {
+            let limit = match parts.negative {
+                true => min,
+                false => max,
+            };
+            let fits = !magnitude.truncated && magnitude.low <= limit;
+            match (fits, parts.negative, opts.overflow) {
+                (true, true, _) => (negate(magnitude.low), false),
+                (true, false, _) => (magnitude.low, false),
+                (false, negative, Overflow::Error) => return overflow(negative, false),
+                (false, true, Overflow::Wrap) => (negate(magnitude.low), true),
+                (false, false, Overflow::Wrap) => (magnitude.low & mask, true),
+                (false, true, Overflow::Saturate) => (min, true),
+                (false, false, Overflow::Saturate) => (max, true),
+                (false, true, Overflow::Truncate) => (negate(magnitude.low & max), true),
+                (false, false, Overflow::Truncate) => (magnitude.low & max, true),
+            }
+        }
+    };
+
+    Ok((from_pattern(pattern, bits, signed), overflowed))
+}
+
+/// Accumulates the digits of `parts` into the low 128 bits of their magnitude.
+fn accumulate_wide<'f, T: PrimInt>(
+    parts: &Parts,
+    fmt: &PrefixFmt<'f>,
+) -> Result<Magnitude, ParseError<'f, T>> {
+    let start = parts.digits_offset();
+    let radix = u128::from(fmt.radix);
+    let mut magnitude = Magnitude {
+        low: 0,
+        truncated: false,
+    };
+    let mut digits = 0;
+
+    for (index, c) in parts.digits.char_indices() {
+        if fmt.separators.chars.contains(&c) {
+            continue;
+        }
+        let Some(digit) = fmt.digit_value(c) else {
+            let error = ParseError::at(ErrorKind::InvalidDigit, start + index, Some(c));
+            return Err(error.with_fmt(fmt));
+        };
+
+        let (shifted, lost) = magnitude.low.overflowing_mul(radix);
+        let (low, carried) = shifted.overflowing_add(u128::from(digit));
+        magnitude = Magnitude {
+            low,
+            truncated: magnitude.truncated || lost || carried,
+        };
+        digits += 1;
+    }
+
+    match digits {
+        0 => Err(ParseError::at(ErrorKind::Empty, start, None).with_fmt(fmt)),
+        _ => Ok(magnitude),
+    }
+}
+
+/// The low `bits` bits set
+fn mask(bits: u32) -> u128 {
+    u128::MAX >> (128 - bits)
+}
+
+/// The integer whose two's-complement representation is `pattern`
+fn from_pattern<T: PrimInt>(pattern: u128, bits: u32, signed: bool) -> T {
+    let negative = signed && pattern >> (bits - 1) & 1 == 1;
+    match negative {
+        true => T::from((pattern | !mask(bits)) as i128),
+        false => T::from(pattern),
+    }
+    .unwrap_or_else(T::zero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Overflow::{self, Error, Saturate, Truncate, Wrap};
+    use crate::{ErrorKind, ParseOptions, PrefixParse};
+
+    type Outcome<T> = Result<(T, bool), ErrorKind>;
+
+    const NEG_UNSIGNED: Outcome<u8> = Err(ErrorKind::NegativeUnsigned);
+
+    /// Each policy, input, and the outcome for `u8` and `i8`
+    #[rustfmt::skip]
+    const MATRIX: &[(Overflow, &str, Outcome<u8>, Outcome<i8>)] = &[
+        (Error, "255", Ok((255, false)), Err(ErrorKind::PosOverflow)),
+        (Error, "0x100", Err(ErrorKind::PosOverflow), Err(ErrorKind::PosOverflow)),
+        (Error, "-1", NEG_UNSIGNED, Ok((-1, false))),
+        (Error, "-129", NEG_UNSIGNED, Err(ErrorKind::NegOverflow)),
+
+        (Wrap, "255", Ok((255, false)), Ok((-1, true))),
+        (Wrap, "0x100", Ok((0, true)), Ok((0, true))),
+        (Wrap, "0x1FF", Ok((255, true)), Ok((-1, true))),
+        (Wrap, "0x80", Ok((128, false)), Ok((-128, true))),
+        (Wrap, "-1", Ok((255, true)), Ok((-1, false))),
+        (Wrap, "-128", Ok((128, true)), Ok((-128, false))),
+        (Wrap, "-129", Ok((127, true)), Ok((127, true))),
+        (Wrap, "-0x181", Ok((127, true)), Ok((127, true))),
+
+        (Saturate, "255", Ok((255, false)), Ok((127, true))),
+        (Saturate, "0x100", Ok((255, true)), Ok((127, true))),
+        (Saturate, "0x80", Ok((128, false)), Ok((127, true))),
+        (Saturate, "-1", Ok((0, true)), Ok((-1, false))),
+        (Saturate, "-128", Ok((0, true)), Ok((-128, false))),
+        (Saturate, "-129", Ok((0, true)), Ok((-128, true))),
+
+        (Truncate, "255", Ok((255, false)), Ok((127, true))),
+        (Truncate, "0x100", Ok((0, true)), Ok((0, true))),
+        (Truncate, "0x17F", Ok((127, true)), Ok((127, true))),
+        // the magnitude of an i8 keeps 7 bits, so the sign bit alone truncates to zero
+        (Truncate, "0x80", Ok((128, false)), Ok((0, true))),
+        (Truncate, "-0x80", NEG_UNSIGNED, Ok((-128, false))),
+        (Truncate, "-0x100", NEG_UNSIGNED, Ok((0, true))),
+        (Truncate, "-129", NEG_UNSIGNED, Ok((-1, true))),
+        (Truncate, "-1", NEG_UNSIGNED, Ok((-1, false))),
+    ];
+
+    #[test]
+    fn overflow_matrix() {
+        for &(policy, src, unsigned, signed) in MATRIX {
+            let opts = ParseOptions::new().overflow(policy);
+            let parse_u8 = u8::parse_overflowing(&opts, src).map_err(|error| error.kind());
+            let parse_i8 = i8::parse_overflowing(&opts, src).map_err(|error| error.kind());
+            assert_eq!(parse_u8, unsigned, "{policy:?} u8 {src}");
+            assert_eq!(parse_i8, signed, "{policy:?} i8 {src}");
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+
+use num_traits::{Num, PrimInt};
+
+use crate::{DEC, Detected, LetterCase, ParseError, PrefixParse};
+
+/// A number that remembers the notation it was written in.
+///
+/// Parses with [`FromStr`], so it works with generic code calling [`str::parse`], such as clap
+/// derive, CSV readers or environment helpers. Displays in the notation it was parsed from, and
+/// compares, orders and hashes by value alone.
+///
+/// # Example
+/// ```
+/// use prefix_parse::Prefixed;
+///
+/// let mut addr: Prefixed<u32> = "0X00ff".parse()?;
+/// assert_eq!(*addr, 255);
+///
+/// *addr += 1;
+/// assert_eq!(addr.to_string(), "0X0100");
+/// assert_eq!(addr.into_inner(), 256);
+///
+/// assert_eq!(Prefixed::from(42u8).to_string(), "42");
+/// # Ok::<(), prefix_parse::ParseError<u32>>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Prefixed<T> {
+    detected: Detected<'static, T>,
+}
+
+impl<T> Prefixed<T> {
+    /// The value, along with the notation it was written in
+    pub fn detected(&self) -> &Detected<'static, T> {
+        &self.detected
+    }
+
+    /// Unwraps the value
+    pub fn into_inner(self) -> T {
+        self.detected.value
+    }
+}
+
+impl<T> From<T> for Prefixed<T> {
+    /// Wraps a value, written in decimal
+    fn from(value: T) -> Self {
+        Prefixed {
+            detected: Detected {
+                value,
+                fmt: &DEC,
+                prefix: DEC.prefix(),
+                prefix_case: LetterCase::Uncased,
+                digits: 0,
+                digit_case: LetterCase::Uncased,
+            },
+        }
+    }
+}
+
+impl<T> From<Detected<'static, T>> for Prefixed<T> {
+    fn from(detected: Detected<'static, T>) -> Self {
+        Prefixed { detected }
+    }
+}
+
+impl<T: Num> FromStr for Prefixed<T> {
+    type Err = ParseError<'static, T>;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        T::parse_detect(src).map(Prefixed::from)
+    }
+}
+
+impl<T: PrimInt> fmt::Display for Prefixed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.detected.display().fmt(f)
+    }
+}
+
+impl<T> Deref for Prefixed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.detected.value
+    }
+}
+
+impl<T> DerefMut for Prefixed<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.detected.value
+    }
+}
+
+impl<T: PartialEq> PartialEq for Prefixed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.detected.value == other.detected.value
+    }
+}
+
+impl<T: Eq> Eq for Prefixed<T> {}
+
+impl<T: PartialOrd> PartialOrd for Prefixed<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.detected.value.partial_cmp(&other.detected.value)
+    }
+}
+
+impl<T: Ord> Ord for Prefixed<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.detected.value.cmp(&other.detected.value)
+    }
+}
+
+impl<T: Hash> Hash for Prefixed<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.detected.value.hash(state);
+    }
+}
+
+/// Serializes as a string in the notation it was written in, and deserializes from a native
+/// integer or a string in any notation [`PrefixParse::parse`] accepts.
+///
+/// # Example
+/// ```
+/// use prefix_parse::Prefixed;
+///
+/// let addr: Prefixed<u32> = serde_json::from_str(r#""0x4000""#)?;
+/// assert_eq!(*addr, 0x4000);
+/// assert_eq!(serde_json::to_string(&addr)?, r#""0x4000""#);
+///
+/// let len: Prefixed<u32> = serde_json::from_str("16")?;
+/// assert_eq!(serde_json::to_string(&len)?, r#""16""#);
+///
+/// use serde::{Deserialize, de::value::{Error, U128Deserializer}};
+/// let big = Prefixed::<i128>::deserialize(U128Deserializer::<Error>::new(1 << 100))?;
+/// assert_eq!(*big, 1 << 100);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[cfg(feature = "serde")]
+impl<T: PrimInt> ::serde::Serialize for Prefixed<T> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Num + num_traits::FromPrimitive> ::serde::Deserialize<'de> for Prefixed<T> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(crate::serde::PrefixVisitor::new(None))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Num> crate::serde::Visited<T> for Prefixed<T> {
+    fn parse_str(src: &str) -> Result<Self, ParseError<'static, T>> {
+        src.parse()
+    }
+}
@@ -0,0 +1,68 @@
+//! Python `int(s, 0)` compatible parsing.
+//!
+//! Accepts exactly the strings Python does, so tools sharing files with Python scripts agree on
+//! which are valid: surrounding whitespace, an optional sign, `0x`, `0o` or `0b` prefixes in
+//! either case, and single underscores between digits or directly after the prefix. Decimal
+//! numbers may not start with `0` unless all their digits are zero, so `010` is rejected with
+//! [`ErrorKind::LeadingZero`] where `00` is zero.
+//!
+//! Values of any size parse into types such as `num_bigint::BigInt`, as Python's do. Python also
+//! accepts non-ASCII decimal digits, such as `١٢٣`, which are rejected here.
+//!
+//! # Example
+//! ```
+//! use num_bigint::BigInt;
+//! use prefix_parse::{ErrorKind, python};
+//!
+//! assert_eq!(python::parse(" 0X_FF\n"), Ok(255u32));
+//! assert_eq!(python::parse("-1_000"), Ok(-1000i32));
+//! assert_eq!(python::parse("000"), Ok(0u8));
+//!
+//! let error = python::parse::<u32>("010").unwrap_err();
+//! assert_eq!((error.kind(), error.offset()), (ErrorKind::LeadingZero, 0));
+//! let error = python::parse::<u32>("0 1").unwrap_err();
+//! assert_eq!((error.kind(), error.offset()), (ErrorKind::InvalidDigit, 1));
+//! assert!(python::parse::<u32>("1__000").is_err());
+//! assert!(python::parse::<u32>("_1").is_err());
+//!
+//! let big: BigInt = python::parse("0x1_0000_0000_0000_0000_0000_0000_0000_0000").unwrap();
+//! assert_eq!(big, BigInt::from(1) << 128);
+//! ```
+
+use num_traits::Num;
+
+use crate::{
+    BIN, DEC, ErrorKind, HEX, OCT, ParseError, Parts, PrefixFmt, SINGLE_UNDERSCORES,
+    from_signed_digits,
+};
+
+const HEX_PY: PrefixFmt = HEX.with_separators(SINGLE_UNDERSCORES);
+const OCT_PY: PrefixFmt = OCT.with_separators(SINGLE_UNDERSCORES);
+const BIN_PY: PrefixFmt = BIN.with_separators(SINGLE_UNDERSCORES);
+const DEC_PY: PrefixFmt = DEC.with_separators(SINGLE_UNDERSCORES);
+
+/// Parse `src` as Python's `int(src, 0)` does
+///
+/// # Errors
+/// As [`PrefixParse::parse`](crate::PrefixParse::parse), with [`ErrorKind::LeadingZero`] for
+/// decimal numbers starting with `0` that are not zero.
+pub fn parse<T: Num>(src: &str) -> Result<T, ParseError<'static, T>> {
+    let body = src.trim_start();
+    let skipped = src.len() - body.len();
+    let body = body.trim_end();
+
+    let (fmt, parts) = [&HEX_PY, &OCT_PY, &BIN_PY]
+        .into_iter()
+        .find_map(|fmt| fmt.split(body).map(|parts| (fmt, parts)))
+        .unwrap_or_else(|| (&DEC_PY, Parts::unprefixed(body)));
+
+    // other characters are invalid digits, reported where they are
+    let decimal = parts.digits.chars().all(|c| c.is_ascii_digit() || c == '_');
+    let mut digits = parts.digits.chars().filter(|&c| c != '_');
+    if !parts.prefixed() && decimal && digits.next() == Some('0') && digits.any(|c| c != '0') {
+        let offset = skipped + parts.digits_offset();
+        return Err(ParseError::at(ErrorKind::LeadingZero, offset, Some('0')).with_fmt(fmt));
+    }
+
+    from_signed_digits(&parts, fmt).map_err(|error| error.offset_by(skipped))
+}
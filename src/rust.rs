@@ -0,0 +1,201 @@
+//! Rust integer literal parsing, including type suffixes.
+//!
+//! Accepts the integer literal grammar of Rust source: `0x`, `0o` and `0b` prefixes in lowercase,
+//! underscores anywhere after the first digit or the prefix, and an optional type suffix such as
+//! `u8` or `i64`. A leading `-` negates the literal, as negation does in Rust source.
+//!
+//! # Example
+//! ```
+//! use prefix_parse::{ErrorKind, rust::{self, IntType, Literal}};
+//!
+//! assert_eq!(rust::parse("255u8"), Ok(Literal { value: 255u64, suffix: Some(IntType::U8) }));
+//! assert_eq!(rust::parse("0xFF_i64"), Ok(Literal { value: 255, suffix: Some(IntType::I64) }));
+//! assert_eq!(rust::parse("0b__1__"), Ok(Literal { value: 1u8, suffix: None }));
+//! assert_eq!(rust::parse::<i32>("-128i8").map(|literal| literal.value), Ok(-128));
+//!
+//! let error = rust::parse::<u64>("0x100u8").unwrap_err();
+//! assert_eq!(error.kind(), ErrorKind::PosWidthOverflow { bits: 8, signed: false });
+//! assert!(error.to_string().ends_with("Max 0xff"));
+//!
+//! let error = rust::parse::<u64>("10u7").unwrap_err();
+//! assert_eq!((error.kind(), error.offset()), (ErrorKind::InvalidSuffix, 2));
+//! let error = rust::parse::<u64>("0o8").unwrap_err();
+//! assert_eq!((error.kind(), error.offset()), (ErrorKind::InvalidDigit, 2));
+//! let error = rust::parse::<u64>("0b12").unwrap_err();
+//! assert_eq!((error.kind(), error.offset()), (ErrorKind::InvalidDigit, 3));
+//! ```
+
+use core::fmt;
+
+use num_traits::PrimInt;
+
+use crate::{
+    BIN_LOWER, DEC, ErrorKind, HEX_LOWER, OCT_LOWER, ParseError, ParseOptions, PrefixFmt,
+    UNDERSCORES, options,
+};
+
+const HEX_RS: PrefixFmt = HEX_LOWER.with_separators(UNDERSCORES);
+const OCT_RS: PrefixFmt = OCT_LOWER.with_separators(UNDERSCORES);
+const BIN_RS: PrefixFmt = BIN_LOWER.with_separators(UNDERSCORES);
+const DEC_RS: PrefixFmt = DEC.with_separators(UNDERSCORES);
+
+/// A parsed integer literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Literal<T> {
+    /// The value of the literal, negated if preceded by `-`
+    pub value: T,
+    /// The type suffix, if written
+    pub suffix: Option<IntType>,
+}
+
+/// The primitive integer types, as written in literal suffixes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntType {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+}
+
+impl IntType {
+    const ALL: [IntType; 12] = [
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::Usize,
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Isize,
+    ];
+
+    /// The type with the name `name`, such as `u8`
+    pub fn from_name(name: &str) -> Option<Self> {
+        IntType::ALL.into_iter().find(|ty| ty.name() == name)
+    }
+
+    /// The name of the type, such as `u8`
+    pub const fn name(self) -> &'static str {
+        match self {
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+        }
+    }
+
+    /// The width of the type in bits, for the target platform
+    pub const fn bits(self) -> u32 {
+        match self {
+            IntType::U8 | IntType::I8 => u8::BITS,
+            IntType::U16 | IntType::I16 => u16::BITS,
+            IntType::U32 | IntType::I32 => u32::BITS,
+            IntType::U64 | IntType::I64 => u64::BITS,
+            IntType::U128 | IntType::I128 => u128::BITS,
+            IntType::Usize | IntType::Isize => usize::BITS,
+        }
+    }
+
+    /// If true, the type holds negative values
+    pub const fn is_signed(self) -> bool {
+        matches!(
+            self,
+            IntType::I8
+                | IntType::I16
+                | IntType::I32
+                | IntType::I64
+                | IntType::I128
+                | IntType::Isize
+        )
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parse `src` as a Rust integer literal, with an optional type suffix
+///
+/// # Errors
+/// As [`PrefixParse::parse`](crate::PrefixParse::parse), and:
+/// - [`ErrorKind::InvalidSuffix`] if the digits are followed by anything but a type suffix.
+/// - [`ErrorKind::PosWidthOverflow`], [`ErrorKind::NegWidthOverflow`] or
+///   [`ErrorKind::NegativeUnsigned`] if the value does not fit the suffixed type.
+pub fn parse<T: PrimInt>(src: &str) -> Result<Literal<T>, ParseError<'static, T>> {
+    if src.starts_with('+') {
+        return Err(ParseError::at(ErrorKind::MisplacedSign, 0, Some('+')));
+    }
+
+    let (fmt, (parts, rest)) = [&HEX_RS, &OCT_RS, &BIN_RS, &DEC_RS]
+        .into_iter()
+        .find_map(|fmt| fmt.split_partial(src).map(|split| (fmt, split)))
+        .ok_or_else(|| DEC_RS.mismatch(src, true))?;
+
+    // digits of a larger radix, such as `2` in `0b12` or `8` in `0o8`
+    let offset = src.len() - rest.len();
+    if let Some(c) = rest.chars().next().filter(char::is_ascii_digit) {
+        return Err(ParseError::at(ErrorKind::InvalidDigit, offset, Some(c)).with_fmt(fmt));
+    }
+
+    let (value, _) = options::parse_parts::<T>(&parts, fmt, &ParseOptions::new())?;
+    let suffix = match rest {
+        "" => None,
+        _ => match IntType::from_name(rest) {
+            Some(suffix) => Some(suffix),
+            None => {
+                let found = rest.chars().next();
+                let error = ParseError::at(ErrorKind::InvalidSuffix, offset, found);
+                return Err(error.with_fmt(fmt));
+            }
+        },
+    };
+
+    match suffix.and_then(|suffix| misfit(value, suffix)) {
+        Some(ErrorKind::NegativeUnsigned) => {
+            Err(ParseError::at(ErrorKind::NegativeUnsigned, 0, Some('-')).with_fmt(fmt))
+        }
+        Some(kind) => Err(ParseError::at(kind, parts.digits_offset(), None).with_fmt(fmt)),
+        None => Ok(Literal { value, suffix }),
+    }
+}
+
+/// Why `value` does not fit `ty`, if it does not
+fn misfit<T: PrimInt>(value: T, ty: IntType) -> Option<ErrorKind> {
+    let (bits, signed) = (ty.bits(), ty.is_signed());
+    let max = u128::MAX >> (128 - bits + u32::from(signed));
+    let min = match signed {
+        true => -1i128 << (bits - 1),
+        false => 0,
+    };
+
+    match value.to_i128() {
+        Some(value) if value < 0 && !signed => Some(ErrorKind::NegativeUnsigned),
+        Some(value) if value < min => Some(ErrorKind::NegWidthOverflow { bits }),
+        _ if value.to_u128().is_some_and(|value| value > max) => {
+            Some(ErrorKind::PosWidthOverflow { bits, signed })
+        }
+        _ => None,
+    }
+}
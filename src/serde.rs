@@ -0,0 +1,199 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! Use the modules with `#[serde(with = "...")]`. Each deserializes either a native integer or a
+//! string in any notation [`PrefixParse::parse`] accepts, and serializes in its own notation.
+//!
+//! - [`prefix_parse::serde`](self) serializes native integers.
+//! - [`hex`], [`oct`] and [`bin`] serialize prefixed strings, such as `"0x4000"`.
+//!
+//! For other formats, call [`serialize_with`] and [`deserialize_with`] from your own functions.
+//!
+//! # Example
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Deserialize, Serialize)]
+//! struct Region {
+//!     #[serde(with = "prefix_parse::serde::hex")]
+//!     base: u32,
+//!     #[serde(with = "prefix_parse::serde")]
+//!     len: u32,
+//! }
+//!
+//! let region: Region = serde_json::from_str(r#"{ "base": "0x4000", "len": "0b1010" }"#)?;
+//! assert_eq!(region, Region { base: 0x4000, len: 10 });
+//!
+//! let region: Region = serde_json::from_str(r#"{ "base": 16384, "len": 10 }"#)?;
+//! assert_eq!(serde_json::to_string(&region)?, r#"{"base":"0x4000","len":10}"#);
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::serde::de::{self, Deserializer, Unexpected, Visitor};
+use ::serde::{Serialize, Serializer};
+use num_traits::{FromPrimitive, Num, PrimInt};
+
+use crate::{ParseError, PrefixFmt, PrefixParse};
+
+/// Serialize a value as a native integer
+pub fn serialize<T: Serialize, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    value.serialize(serializer)
+}
+
+/// Deserialize a value from a native integer, or a string in any notation [`PrefixParse::parse`]
+/// accepts
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Num + FromPrimitive,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(PrefixVisitor::new(None))
+}
+
+/// Serialize a value as a string in `fmt`
+///
+/// # Example
+/// ```
+/// use prefix_parse::{PrefixFmt, serde::serialize_with};
+///
+/// const BASE36: PrefixFmt = match PrefixFmt::new("0z", 36) {
+///     Ok(fmt) => fmt,
+///     Err(_) => panic!("invalid format"),
+/// };
+///
+/// fn base36<S: serde::Serializer>(value: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+///     serialize_with(&BASE36, value, serializer)
+/// }
+///
+/// #[derive(serde::Serialize)]
+/// struct Id(#[serde(serialize_with = "base36")] u32);
+///
+/// assert_eq!(serde_json::to_string(&Id(2015))?, r#""0z1jz""#);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub fn serialize_with<T, S>(fmt: &PrefixFmt, value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: PrimInt,
+    S: Serializer,
+{
+    serializer.collect_str(&fmt.display(*value))
+}
+
+/// Deserialize a value from a native integer, or a string in `fmt`
+pub fn deserialize_with<'de, T, D>(fmt: &PrefixFmt, deserializer: D) -> Result<T, D::Error>
+where
+    T: Num + FromPrimitive,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(PrefixVisitor::new(Some(fmt)))
+}
+
+macro_rules! prefixed_module {
+    ($module:ident, $fmt:ident, $example:literal) => {
+        #[doc = concat!(
+            "Serialize as `", $example, "` strings, with [`", stringify!($fmt), "`](crate::",
+            stringify!($fmt), ")"
+        )]
+        ///
+        /// Deserializes native integers and strings in any notation, like
+        /// [`deserialize`](crate::serde::deserialize).
+        pub mod $module {
+            use num_traits::{FromPrimitive, Num, PrimInt};
+            use ::serde::{Deserializer, Serializer};
+
+            #[doc = concat!("Serialize a value as a `", $example, "` string")]
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: PrimInt,
+                S: Serializer,
+            {
+                super::serialize_with(&crate::$fmt, value, serializer)
+            }
+
+            /// Deserialize a value from a native integer, or a string in any notation
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: Num + FromPrimitive,
+                D: Deserializer<'de>,
+            {
+                super::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+prefixed_module!(hex, HEX, "0x..");
+prefixed_module!(oct, OCT, "0o..");
+prefixed_module!(bin, BIN, "0b..");
+
+/// Accepts native integers, and strings in `fmt` or any built-in notation, producing a `V` from
+/// the parsed `T`
+pub(crate) struct PrefixVisitor<'f, T, V = T> {
+    fmt: Option<&'f PrefixFmt<'f>>,
+    marker: PhantomData<(T, V)>,
+}
+
+impl<'f, T, V> PrefixVisitor<'f, T, V> {
+    pub(crate) fn new(fmt: Option<&'f PrefixFmt<'f>>) -> Self {
+        PrefixVisitor {
+            fmt,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A value deserialized by [`PrefixVisitor`], from a `T` read natively or parsed from a string
+pub(crate) trait Visited<T: Num>: From<T> {
+    /// Parse `src` in any notation [`PrefixParse::parse`] accepts
+    fn parse_str(src: &str) -> Result<Self, ParseError<'static, T>>;
+}
+
+impl<T: Num> Visited<T> for T {
+    fn parse_str(src: &str) -> Result<Self, ParseError<'static, T>> {
+        T::parse(src)
+    }
+}
+
+impl<'de, T: Num + FromPrimitive, V: Visited<T>> Visitor<'de> for PrefixVisitor<'_, T, V> {
+    type Value = V;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.fmt {
+            Some(fmt) => write!(f, "an integer, or a string in radix {}", fmt.radix()),
+            None => f.write_str("an integer, or a string like 0x.., 0o.., 0b.. or decimal"),
+        }
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<V, E> {
+        T::from_i64(value)
+            .map(V::from)
+            .ok_or_else(|| E::invalid_value(Unexpected::Signed(value), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<V, E> {
+        T::from_u64(value)
+            .map(V::from)
+            .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<V, E> {
+        T::from_i128(value)
+            .map(V::from)
+            .ok_or_else(|| E::custom(format_args!("{value} is out of range")))
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<V, E> {
+        T::from_u128(value)
+            .map(V::from)
+            .ok_or_else(|| E::custom(format_args!("{value} is out of range")))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<V, E> {
+        match self.fmt {
+            Some(fmt) => T::parse_with(fmt, value).map(V::from).map_err(E::custom),
+            None => V::parse_str(value).map_err(E::custom),
+        }
+    }
+}
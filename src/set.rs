@@ -0,0 +1,301 @@
+use num_traits::Num;
+
+use crate::{BIN, DEC, ErrorKind, HEX, OCT, ParseError, Parts, PrefixFmt, SetError, split_sign};
+
+/// A set of formats to detect between, such as the `0x`, `0o` and `0b` formats of
+/// [`PrefixParse::parse`](crate::PrefixParse::parse).
+///
+/// The format whose prefix matches the most of the input wins, so `0x` beats a C-style `0` octal
+/// prefix. Ties go to the longer suffix. A format whose affixes leave no digits, and could be read
+/// as digits of the fallback, only wins if nothing else matches, so `0` is zero rather than an
+/// empty octal number. Input that no format
+/// matches is parsed with the fallback format, decimal by default.
+///
+/// # Example
+/// ```
+/// use prefix_parse::{PrefixFmt, PrefixParse, PrefixSet, BIN, HEX};
+///
+/// const BASE36: PrefixFmt = match PrefixFmt::new("0z", 36) {
+///     Ok(fmt) => fmt,
+///     Err(_) => panic!("invalid format"),
+/// };
+/// const SET: PrefixSet = match PrefixSet::new(&[&HEX, &BIN, &BASE36]) {
+///     Ok(set) => set,
+///     Err(_) => panic!("conflicting formats"),
+/// };
+///
+/// assert_eq!(u32::parse_in(&SET, "0z1jz"), Ok(2015));
+/// assert_eq!(u32::parse_in(&SET, "0xFF"), Ok(255));
+/// assert_eq!(u32::parse_in(&SET, "42"), Ok(42));
+/// assert!(u32::parse_in(&SET, "0o17").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefixSet<'a> {
+    fmts: &'a [&'a PrefixFmt<'a>],
+    fallback: Option<&'a PrefixFmt<'a>>,
+}
+
+impl<'a> PrefixSet<'a> {
+    /// The formats detected by [`PrefixParse::parse`](crate::PrefixParse::parse): `0x`, `0o`
+    /// and `0b`, falling back to decimal
+    pub const BUILTIN: PrefixSet<'static> = PrefixSet {
+        fmts: &[&HEX, &OCT, &BIN],
+        fallback: Some(&DEC),
+    };
+
+    /// Create a set detecting between `fmts`, falling back to decimal
+    ///
+    /// # Errors
+    /// [`SetError::Conflict`] if two formats share a spelling of both their prefix and suffix, so
+    /// that neither can win. Spellings are compared regardless of case if either format is case
+    /// insensitive.
+    ///
+    /// # Example
+    /// ```
+    /// use prefix_parse::{ErrorKind, PrefixFmt, PrefixParse, PrefixSet, SetError, HEX};
+    ///
+    /// let c_oct = PrefixFmt::new("0", 8)?;
+    /// let fmts = [&HEX, &c_oct];
+    /// let set = PrefixSet::new(&fmts).unwrap();
+    /// assert_eq!(u32::parse_in(&set, "0x1F"), Ok(31));
+    /// assert_eq!(u32::parse_in(&set, "017"), Ok(15));
+    ///
+    /// // a `0` with no digits after it is decimal zero, rather than an empty octal number
+    /// assert_eq!(u32::parse_in(&set, "0"), Ok(0));
+    /// assert_eq!(i32::parse_in(&set, "-0"), Ok(0));
+    /// // while a bare `0x` is an empty hexadecimal number
+    /// let error = u32::parse_in(&set, "0x").unwrap_err();
+    /// assert_eq!((error.kind(), error.offset()), (ErrorKind::Empty, 2));
+    /// assert_eq!(error.fmt(), Some(&HEX));
+    ///
+    /// let base36 = PrefixFmt::new("0X", 36)?;
+    /// assert_eq!(PrefixSet::new(&[&HEX, &base36]), Err(SetError::Conflict(0, 1)));
+    /// # Ok::<(), prefix_parse::FmtError>(())
+    /// ```
+    pub const fn new(fmts: &'a [&'a PrefixFmt<'a>]) -> Result<Self, SetError> {
+        let mut first = 0;
+        while first < fmts.len() {
+            let mut second = first + 1;
+            while second < fmts.len() {
+                if conflict(fmts[first], fmts[second]) {
+                    return Err(SetError::Conflict(first, second));
+                }
+                second += 1;
+            }
+            first += 1;
+        }
+
+        Ok(PrefixSet {
+            fmts,
+            fallback: Some(&DEC),
+        })
+    }
+
+    /// Parse input that no format matches with `fallback`
+    pub const fn with_fallback(self, fallback: &'a PrefixFmt<'a>) -> Self {
+        PrefixSet {
+            fallback: Some(fallback),
+            ..self
+        }
+    }
+
+    /// Reject input that no format matches, with [`ErrorKind::NoPrefixMatch`]
+    pub const fn without_fallback(self) -> Self {
+        PrefixSet {
+            fallback: None,
+            ..self
+        }
+    }
+
+    /// The formats to detect between
+    pub const fn fmts(&self) -> &'a [&'a PrefixFmt<'a>] {
+        self.fmts
+    }
+
+    /// The format for input that no format matches, if any
+    pub const fn fallback(&self) -> Option<&'a PrefixFmt<'a>> {
+        self.fallback
+    }
+
+    /// Splits `src` with the format whose affixes match the most of it, or with the fallback
+    pub(crate) fn split<'s>(&self, src: &'s str) -> Option<(&'a PrefixFmt<'a>, Parts<'s>)> {
+        choose(self.fmts.iter().copied(), self.fallback, src)
+    }
+}
+
+impl<'a> PrefixMatcher<'a> for PrefixSet<'a> {
+    fn detect(&self, src: &str) -> Option<&'a PrefixFmt<'a>> {
+        self.split(src).map(|(fmt, _)| fmt)
+    }
+
+    fn fallback(&self) -> Option<&'a PrefixFmt<'a>> {
+        self.fallback
+    }
+}
+
+/// Chooses the format of a number among a set of formats, for
+/// [`PrefixParse::parse_in`](crate::PrefixParse::parse_in).
+///
+/// [`PrefixSet`] scans its formats in turn, which suits a handful of formats. For dozens of
+/// formats, compile the set into a [`PrefixTrie`](crate::PrefixTrie), whose cost does not grow
+/// with the number of formats. Both choose the same format for any input.
+pub trait PrefixMatcher<'a> {
+    /// The format whose affixes match the most of `src`, or the fallback if none match and the
+    /// fallback does, as described for [`PrefixSet`]
+    fn detect(&self, src: &str) -> Option<&'a PrefixFmt<'a>>;
+
+    /// The format for input that no format matches, if any
+    fn fallback(&self) -> Option<&'a PrefixFmt<'a>>;
+}
+
+/// The error for no format of `matcher` matching `src`
+pub(crate) fn mismatch<'a, T: Num>(
+    matcher: &impl PrefixMatcher<'a>,
+    src: &str,
+) -> ParseError<'a, T> {
+    match matcher.fallback() {
+        Some(fallback) => fallback.mismatch(src, false),
+        None => {
+            let offset = src.len() - split_sign(src).1.len();
+            let found = src[offset..].chars().next();
+            ParseError::at(ErrorKind::NoPrefixMatch, offset, found)
+        }
+    }
+}
+
+/// Splits `src` with the best of `candidates`, in set order, as described for [`PrefixSet`]
+///
+/// A format whose affixes leave no digits, and could themselves be read as digits of the
+/// fallback, loses to any format that leaves some, and to the fallback. So a C-style `0` octal
+/// prefix does not swallow the number `0`, while a bare `0x` is still an empty hexadecimal number.
+pub(crate) fn choose<'a, 's>(
+    candidates: impl IntoIterator<Item = &'a PrefixFmt<'a>>,
+    fallback: Option<&'a PrefixFmt<'a>>,
+    src: &'s str,
+) -> Option<(&'a PrefixFmt<'a>, Parts<'s>)> {
+    let hollow = |parts: &Parts| {
+        let suffix = &parts.src[parts.digits_offset() + parts.digits.len()..];
+        parts.digits.is_empty()
+            && fallback.is_some_and(|fallback| {
+                parts
+                    .prefix
+                    .chars()
+                    .chain(suffix.chars())
+                    .all(|c| fallback.digit_value(c).is_some())
+            })
+    };
+    let rank = |parts: &Parts| (!hollow(parts), affix_lens(parts));
+
+    let mut best: Option<(&'a PrefixFmt<'a>, Parts<'s>)> = None;
+    for fmt in candidates {
+        let Some(parts) = fmt.split(src) else {
+            continue;
+        };
+        match &best {
+            Some((_, chosen)) if rank(chosen) >= rank(&parts) => {}
+            _ => best = Some((fmt, parts)),
+        }
+    }
+
+    match best {
+        Some((_, ref parts)) if !hollow(parts) => best,
+        _ => fallback
+            .and_then(|fallback| fallback.split(src).map(|parts| (fallback, parts)))
+            .or(best),
+    }
+}
+
+/// The lengths of the prefix and suffix of `parts`, as written
+pub(crate) fn affix_lens(parts: &Parts) -> (usize, usize) {
+    let end = parts.digits_offset() + parts.digits.len();
+    (parts.prefix.len(), parts.src.len() - end)
+}
+
+/// Returns true if `a` and `b` share a spelling of both their prefix and suffix
+const fn conflict(a: &PrefixFmt, b: &PrefixFmt) -> bool {
+    let case_sensitive = a.case_sensitive && b.case_sensitive;
+    shares_spelling(a.prefix, a.aliases, b.prefix, b.aliases, case_sensitive)
+        && shares_spelling(
+            a.suffix,
+            a.suffix_aliases,
+            b.suffix,
+            b.suffix_aliases,
+            case_sensitive,
+        )
+}
+
+/// Returns true if any spelling of `a` or its aliases is a spelling of `b` or its aliases
+const fn shares_spelling(
+    a: &str,
+    a_aliases: &[&str],
+    b: &str,
+    b_aliases: &[&str],
+    case_sensitive: bool,
+) -> bool {
+    let mut i = 0;
+    while i <= a_aliases.len() {
+        let a = match i {
+            0 => a,
+            _ => a_aliases[i - 1],
+        };
+        let mut j = 0;
+        while j <= b_aliases.len() {
+            let b = match j {
+                0 => b,
+                _ => b_aliases[j - 1],
+            };
+            if same_spelling(a.as_bytes(), b.as_bytes(), case_sensitive) {
+                return true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn same_spelling(a: &[u8], b: &[u8], case_sensitive: bool) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut index = 0;
+    while index < a.len() {
+        match case_sensitive {
+            true if a[index] != b[index] => return false,
+            false if !a[index].eq_ignore_ascii_case(&b[index]) => return false,
+            _ => {}
+        }
+        index += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BIN, ErrorKind, HEX, PrefixParse};
+
+    #[test]
+    fn bare_prefix_is_empty() {
+        for (src, fmt) in [("0x", &HEX), ("0b", &BIN)] {
+            let error = u32::parse(src).unwrap_err();
+            assert_eq!(
+                (error.kind(), error.offset()),
+                (ErrorKind::Empty, 2),
+                "{src}"
+            );
+            assert_eq!(error.fmt(), Some(fmt), "{src}");
+        }
+
+        let error = i32::parse("-0x").unwrap_err();
+        assert_eq!((error.kind(), error.offset()), (ErrorKind::Empty, 3));
+        assert_eq!(error.fmt(), Some(&HEX));
+
+        let error = u32::parse("-0x").unwrap_err();
+        assert_eq!(
+            (error.kind(), error.offset()),
+            (ErrorKind::NegativeUnsigned, 0)
+        );
+        assert_eq!(error.fmt(), Some(&HEX));
+    }
+}
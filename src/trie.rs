@@ -0,0 +1,146 @@
+use alloc::vec::Vec;
+
+use crate::set::choose;
+use crate::{PrefixFmt, PrefixMatcher, PrefixSet, split_sign};
+
+/// A [`PrefixSet`] compiled into tries of its affix spellings, enabled by the `alloc` feature.
+///
+/// Detection walks one trie along the prefix spellings from the start of the input, and another
+/// along the reversed suffix spellings of formats without a prefix from its end. Its cost depends
+/// on the length of the matching affixes rather than on the number of formats, for prefixed and
+/// suffixed formats alike. Chooses the same format as the set it was compiled from for any input.
+///
+/// # Example
+/// ```
+/// use prefix_parse::{PrefixFmt, PrefixParse, PrefixSet, PrefixTrie, BIN, HEX, OCT};
+///
+/// let motorola = PrefixFmt::new("$", 16)?;
+/// let intel = PrefixFmt::suffixed("h", 16)?.case_insensitive();
+/// let fmts = [&HEX, &OCT, &BIN, &motorola, &intel];
+/// let trie = PrefixTrie::new(&PrefixSet::new(&fmts).unwrap());
+///
+/// assert_eq!(u32::parse_in(&trie, "$FF"), Ok(255));
+/// assert_eq!(u32::parse_in(&trie, "0FFH"), Ok(255));
+/// assert_eq!(u32::parse_in(&trie, "0b11"), Ok(3));
+/// assert_eq!(u32::parse_in(&trie, "17"), Ok(17));
+/// # Ok::<(), prefix_parse::FmtError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixTrie<'a> {
+    set: PrefixSet<'a>,
+    /// The prefix spellings of the formats
+    prefixes: Spellings,
+    /// The reversed suffix spellings of the formats with an empty prefix spelling
+    suffixes: Spellings,
+}
+
+/// A trie of spellings, keyed by their ASCII-lowercased bytes
+#[derive(Debug, Clone)]
+struct Spellings {
+    nodes: Vec<Node>,
+}
+
+/// A node of a trie, reached by the bytes of the spellings leading to it
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// The next byte and the node it leads to, sorted by byte
+    edges: Vec<(u8, usize)>,
+    /// The indices of the formats with a spelling ending here, in set order
+    fmts: Vec<usize>,
+}
+
+impl<'a> PrefixTrie<'a> {
+    /// Compile the affix spellings of `set` into tries
+    pub fn new(set: &PrefixSet<'a>) -> Self {
+        let mut trie = PrefixTrie {
+            set: *set,
+            prefixes: Spellings::new(),
+            suffixes: Spellings::new(),
+        };
+
+        for (index, fmt) in set.fmts().iter().enumerate() {
+            for prefix in core::iter::once(fmt.prefix()).chain(fmt.aliases().iter().copied()) {
+                if !prefix.is_empty() {
+                    trie.prefixes.insert(prefix.bytes(), index);
+                    continue;
+                }
+                for suffix in
+                    core::iter::once(fmt.suffix()).chain(fmt.suffix_aliases().iter().copied())
+                {
+                    trie.suffixes.insert(suffix.bytes().rev(), index);
+                }
+            }
+        }
+        trie
+    }
+
+    /// The set the trie was compiled from
+    pub fn set(&self) -> &PrefixSet<'a> {
+        &self.set
+    }
+}
+
+impl Spellings {
+    fn new() -> Self {
+        Spellings {
+            nodes: alloc::vec![Node::default()],
+        }
+    }
+
+    fn insert(&mut self, spelling: impl Iterator<Item = u8>, fmt: usize) {
+        let mut node = 0;
+        for byte in spelling.map(|byte| byte.to_ascii_lowercase()) {
+            node = match self.edge(node, byte) {
+                Ok(next) => next,
+                Err(slot) => {
+                    let next = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].edges.insert(slot, (byte, next));
+                    next
+                }
+            };
+        }
+
+        let fmts = &mut self.nodes[node].fmts;
+        if !fmts.contains(&fmt) {
+            fmts.push(fmt);
+            fmts.sort_unstable();
+        }
+    }
+
+    /// The node `byte` leads to from `node`, or where its edge would be inserted
+    fn edge(&self, node: usize, byte: u8) -> Result<usize, usize> {
+        let edges = &self.nodes[node].edges;
+        edges
+            .binary_search_by_key(&byte, |&(byte, _)| byte)
+            .map(|slot| edges[slot].1)
+    }
+
+    /// The indices of the formats with a spelling that `bytes` starts with, shortest first
+    fn matches<'s>(
+        &'s self,
+        mut bytes: impl Iterator<Item = u8> + 's,
+    ) -> impl Iterator<Item = usize> + 's {
+        core::iter::successors(Some(0), move |&node| {
+            self.edge(node, bytes.next()?.to_ascii_lowercase()).ok()
+        })
+        .flat_map(|node| self.nodes[node].fmts.iter().copied())
+    }
+}
+
+impl<'a> PrefixMatcher<'a> for PrefixTrie<'a> {
+    fn detect(&self, src: &str) -> Option<&'a PrefixFmt<'a>> {
+        // every format whose affixes can match, as the set would choose between them
+        let fmts = self.set.fmts();
+        let candidates = self
+            .prefixes
+            .matches(split_sign(src).1.bytes())
+            .chain(self.suffixes.matches(src.bytes().rev()))
+            .map(|index| fmts[index]);
+        choose(candidates, self.set.fallback(), src).map(|(fmt, _)| fmt)
+    }
+
+    fn fallback(&self) -> Option<&'a PrefixFmt<'a>> {
+        self.set.fallback()
+    }
+}
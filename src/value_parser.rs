@@ -0,0 +1,210 @@
+use core::fmt::{Display, Write};
+use core::ops::{Bound, RangeBounds};
+use std::ffi::OsStr;
+
+use clap::builder::TypedValueParser;
+use clap::error::ErrorKind as ClapErrorKind;
+use clap::{Arg, Command};
+use num_traits::Num;
+
+use crate::{ParseError, PrefixFmt, PrefixParse, PrefixSet, SetError};
+
+/// A clap value parser for prefixed numbers, enabled by the `clap` feature.
+///
+/// By default, numbers are parsed as in [`PrefixParse::parse`]. The accepted formats and values
+/// can be narrowed with [`PrefixValueParser::with_fmts`] and [`PrefixValueParser::with_range`].
+/// Errors point at the offending character, and list the accepted notations.
+///
+/// # Example
+/// ```
+/// use clap::{Arg, Command};
+/// use prefix_parse::{PrefixValueParser, HEX, DEC};
+///
+/// let cmd = Command::new("poke")
+///     .arg(Arg::new("addr").long("addr").value_parser(PrefixValueParser::<u32>::new()))
+///     .arg(
+///         Arg::new("len").long("len").value_parser(
+///             PrefixValueParser::<u16>::new()
+///                 .with_fmts(&[&HEX, &DEC])?
+///                 .with_range(1..=4096),
+///         ),
+///     );
+///
+/// let matches = cmd.clone().try_get_matches_from(["poke", "--addr", "0x4000", "--len", "16"])?;
+/// assert_eq!(matches.get_one::<u32>("addr"), Some(&0x4000));
+/// assert_eq!(matches.get_one::<u16>("len"), Some(&16));
+///
+/// let error = cmd.clone().try_get_matches_from(["poke", "--addr", "0x40G0"]).unwrap_err();
+/// assert!(error.to_string().contains("0x40G0\n      ^"));
+///
+/// let error = cmd.try_get_matches_from(["poke", "--len", "0b1"]).unwrap_err();
+/// assert!(error.to_string().contains("expected 0x.. or decimal"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixValueParser<T> {
+    set: PrefixSet<'static>,
+    range: (Bound<T>, Bound<T>),
+}
+
+impl<T> PrefixValueParser<T> {
+    /// Create a parser that accepts any notation [`PrefixParse::parse`] accepts, and any value
+    pub fn new() -> Self {
+        PrefixValueParser {
+            set: PrefixSet::BUILTIN,
+            range: (Bound::Unbounded, Bound::Unbounded),
+        }
+    }
+
+    /// Accept only numbers in one of `fmts`, detected as in [`PrefixSet`]
+    ///
+    /// # Errors
+    /// [`SetError::Conflict`] if two formats share a prefix and suffix, as for [`PrefixSet::new`].
+    ///
+    /// # Example
+    /// ```
+    /// use clap::{Arg, Command};
+    /// use prefix_parse::{PrefixFmt, PrefixValueParser, DEC, HEX};
+    ///
+    /// const C_OCT: PrefixFmt = match PrefixFmt::new("0", 8) {
+    ///     Ok(fmt) => fmt,
+    ///     Err(_) => panic!("invalid format"),
+    /// };
+    ///
+    /// let parser = PrefixValueParser::<u32>::new().with_fmts(&[&DEC, &C_OCT, &HEX])?;
+    /// let cmd = Command::new("chmod").arg(Arg::new("mode").value_parser(parser));
+    ///
+    /// let matches = cmd.clone().try_get_matches_from(["chmod", "017"])?;
+    /// assert_eq!(matches.get_one::<u32>("mode"), Some(&15));
+    ///
+    /// let error = cmd.try_get_matches_from(["chmod", "zz"]).unwrap_err();
+    /// assert!(error.to_string().contains("'z' At Byte 0, Assuming Radix 10"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_fmts(self, fmts: &'static [&'static PrefixFmt<'static>]) -> Result<Self, SetError> {
+        Ok(PrefixValueParser {
+            set: PrefixSet::new(fmts)?.without_fallback(),
+            ..self
+        })
+    }
+
+    /// Accept only values in `range`
+    pub fn with_range(self, range: impl RangeBounds<T>) -> Self
+    where
+        T: Clone,
+    {
+        PrefixValueParser {
+            range: (range.start_bound().cloned(), range.end_bound().cloned()),
+            ..self
+        }
+    }
+}
+
+impl<T> Default for PrefixValueParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Num> PrefixValueParser<T> {
+    /// Parses `src` in the format detected among the accepted formats
+    fn parse_str(&self, src: &str) -> Result<T, ParseError<'static, T>> {
+        T::parse_in(&self.set, src)
+    }
+
+    /// The accepted notations, such as `0x.., 0o.., 0b.. or decimal`
+    fn hint(&self) -> String {
+        let notations: Vec<_> = self
+            .set
+            .fmts()
+            .iter()
+            .copied()
+            .chain(self.set.fallback())
+            .map(notation)
+            .collect();
+        match notations.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+            None => String::new(),
+        }
+    }
+
+    /// A clap error for `src`, pointing at the character at `offset`, if any
+    fn error(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        src: &str,
+        reason: impl Display,
+        offset: Option<usize>,
+    ) -> clap::Error {
+        let arg = arg.map_or_else(|| "...".to_owned(), ToString::to_string);
+        let mut message = format!("invalid value '{src}' for '{arg}': {reason}\n");
+        if let Some(offset) = offset {
+            let column = src.get(..offset).map_or(0, |head| head.chars().count());
+            let _ = write!(message, "\n  {src}\n  {:column$}^\n", "");
+            let _ = write!(message, "\n  tip: expected {}\n", self.hint());
+        }
+
+        clap::Error::raw(ClapErrorKind::ValueValidation, message).with_cmd(cmd)
+    }
+}
+
+impl<T> TypedValueParser for PrefixValueParser<T>
+where
+    T: Num + PartialOrd + Display + Clone + Send + Sync + 'static,
+{
+    type Value = T;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let src = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(ClapErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        let value = self
+            .parse_str(src)
+            .map_err(|error| self.error(cmd, arg, src, &error, Some(error.offset())))?;
+
+        match self.range.contains(&value) {
+            true => Ok(value),
+            false => {
+                let reason = format!("{value} is out of range ({})", describe(&self.range));
+                Err(self.error(cmd, arg, src, reason, None))
+            }
+        }
+    }
+}
+
+/// How numbers in `fmt` are written, such as `0x..`, `..h` or `decimal`
+fn notation(fmt: &PrefixFmt) -> String {
+    match (fmt.prefix(), fmt.suffix()) {
+        ("", "") if fmt.radix() == 10 => "decimal".to_owned(),
+        ("", "") => format!("radix {}", fmt.radix()),
+        (prefix, suffix) => format!("{prefix}..{suffix}"),
+    }
+}
+
+/// Describes `range` as bounds, such as `>= 1 and <= 4096`
+fn describe<T: Display>(range: &(Bound<T>, Bound<T>)) -> String {
+    let start = match &range.0 {
+        Bound::Included(start) => Some(format!(">= {start}")),
+        Bound::Excluded(start) => Some(format!("> {start}")),
+        Bound::Unbounded => None,
+    };
+    let end = match &range.1 {
+        Bound::Included(end) => Some(format!("<= {end}")),
+        Bound::Excluded(end) => Some(format!("< {end}")),
+        Bound::Unbounded => None,
+    };
+
+    match (start, end) {
+        (Some(start), Some(end)) => format!("{start} and {end}"),
+        (Some(bound), None) | (None, Some(bound)) => bound,
+        (None, None) => "any value".to_owned(),
+    }
+}